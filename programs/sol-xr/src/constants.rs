@@ -13,3 +13,22 @@ pub const MINTING_ROUNDS: u64 = 24; // 6 months
 pub const DURATION: u64 = 21600; // 6 hours
 pub const BOND_PRICE: u64 = 1_000_000_000; // 1 sol
 pub const BOND_MATURITY: u64 = 15_552_000; //  approx 6 months
+pub const PLATFORM_MINT_FEE_CEILING: u64 = 100_000_000; // 10%, hard cap for governance updates
+pub const MAX_PLATFORM_MINT_FEE_CEILING: u64 = 5_000_000_000; // 5 sol, hard cap for governance updates
+pub const MIN_PREMIUM_NAV_RATIO_CEILING: u64 = 10_000_000_000; // 1000%
+pub const NAV_GROWTH_RATE_CEILING: u64 = 1_000_000_000; // 100%
+pub const MAX_MINT_DURATION: u64 = 604_800; // 7 days
+
+pub const MAX_PLATFORM_FEE_SPLITS: usize = 5;
+pub const BASIS_POINTS: u64 = 10_000; // 100%
+pub const REDEMPTION_FEE: u64 = 10_000_000; // 1%
+pub const REDEMPTION_FEE_CEILING: u64 = 100_000_000; // 10%
+pub const ORACLE_MAX_STALENESS_CEILING: u64 = 3_600; // 1 hour
+pub const ORACLE_MAX_CONFIDENCE_CEILING: u64 = 100_000_000; // 10%
+pub const ORACLE_MAX_DEVIATION_CEILING: u64 = 500_000_000; // 50%
+pub const MAX_OBSERVATION_DEVIATION: u64 = 50_000_000; // 5%, per price observation
+pub const REDEMPTION_CAP_PER_EPOCH: u64 = 100_000_000_000; // 100 sol
+pub const GOVERNANCE_DELAY: u64 = 172_800; // 2 days
+pub const MIN_GOVERNANCE_DELAY: u64 = 3_600; // 1 hour
+pub const MAX_GOVERNANCE_DELAY: u64 = 2_592_000; // 30 days
+pub const GOVERNANCE_GRACE_PERIOD: u64 = 1_209_600; // 14 days, a queued action can't be executed after it
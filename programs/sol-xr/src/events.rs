@@ -0,0 +1,405 @@
+use {
+    crate::state::{
+        auction::DutchAuction, governance_action::TimelockedAction, mint_round::PriceCurve,
+        sol_strategy::FeeSplit,
+    },
+    anchor_lang::prelude::*,
+};
+
+/// SolStrategy parameters that governance can update after initialization
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StrategyParam {
+    PlatformMintFee,
+    MaxPlatformMintFee,
+    MaxMintPerWallet,
+    MinPremiumNavRatio,
+    NavGrowthRate,
+    MintingRounds,
+    MintDuration,
+    GovernanceDelay,
+}
+
+/// Solvency invariants checked by `check_invariants`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Invariant {
+    /// Treasury lamports cover `sol_in_treasury`, `sol_from_bond` and the treasury rent
+    TreasuryCoverage,
+    /// The edition records of a bond add up to the Sol it reserves
+    BondReserves,
+    /// The Sol reserved by every bond adds up to `sol_from_bond`
+    SolFromBond,
+    /// The Solxr supply doesn't exceed `solxr_issued`
+    SolxrSupply,
+}
+
+#[event]
+pub struct StrategyParamUpdated {
+    pub param: StrategyParam,
+    pub old_value: u64,
+    pub new_value: u64,
+}
+
+#[event]
+pub struct GovernanceActionQueued {
+    pub governance_authority: Pubkey,
+    pub action_id: u64,
+    pub action: TimelockedAction,
+    pub eta: u64,
+}
+
+#[event]
+pub struct GovernanceActionExecuted {
+    pub action_id: u64,
+    pub action: TimelockedAction,
+}
+
+#[event]
+pub struct GovernanceActionCancelled {
+    pub governance_authority: Pubkey,
+    pub action_id: u64,
+}
+
+#[event]
+pub struct GovernanceAuthorityProposed {
+    pub governance_authority: Pubkey,
+    pub pending_governance_authority: Pubkey,
+}
+
+#[event]
+pub struct GovernanceAuthorityAccepted {
+    pub old_governance_authority: Pubkey,
+    pub new_governance_authority: Pubkey,
+}
+
+#[event]
+pub struct GovernanceAuthorityProposalCancelled {
+    pub governance_authority: Pubkey,
+    pub cancelled_governance_authority: Pubkey,
+}
+
+#[event]
+pub struct PlatformAddressUpdated {
+    pub old_platform_address: Pubkey,
+    pub new_platform_address: Pubkey,
+}
+
+#[event]
+pub struct PlatformFeeSplitsUpdated {
+    pub old_platform_fee_splits: Vec<FeeSplit>,
+    pub new_platform_fee_splits: Vec<FeeSplit>,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub old_guardian: Option<Pubkey>,
+    pub new_guardian: Option<Pubkey>,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub authority: Pubkey,
+    pub old_paused: u8,
+    pub new_paused: u8,
+}
+
+#[event]
+pub struct OracleConfigured {
+    pub price_oracle: Option<Pubkey>,
+    pub max_staleness: u64,
+    pub max_confidence: u64,
+    pub max_deviation: u64,
+}
+
+#[event]
+pub struct TwapConfigured {
+    pub twap_window: u64,
+    pub min_observation_interval: u64,
+    pub price_reporter: Option<Pubkey>,
+    pub pool_solxr_vault: Option<Pubkey>,
+    pub pool_sol_vault: Option<Pubkey>,
+}
+
+#[event]
+pub struct PriceObserved {
+    pub reporter: Pubkey,
+    pub timestamp: u64,
+    pub price: u64,
+}
+
+#[event]
+pub struct RedemptionParamsUpdated {
+    pub allow_redemption: bool,
+    pub redemption_fee: u64,
+    pub redemption_cap_per_epoch: u64,
+}
+
+#[event]
+pub struct SolxrRedeemed {
+    pub redeemer: Pubkey,
+    pub solxr_burned: u64,
+    pub sol_returned: u64,
+    pub redemption_fee: u64,
+    pub sol_in_treasury: u64,
+    pub token_supply: u64,
+    pub nav: u64,
+}
+
+#[event]
+pub struct InvariantViolated {
+    pub invariant: Invariant,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+#[event]
+pub struct TreasurySynced {
+    pub treasury_lamports: u64,
+    /// Lamports credited to `sol_in_treasury`
+    pub surplus: u64,
+    pub sol_in_treasury: u64,
+    pub sol_from_bond: u64,
+    pub nav: u64,
+}
+
+#[event]
+pub struct StrategyInitialized {
+    pub governance_authority: Pubkey,
+    pub token: Pubkey,
+    pub initial_pool_cap: u64,
+    pub individual_address_cap: u64,
+}
+
+#[event]
+pub struct Invested {
+    pub investor: Pubkey,
+    pub sol_amount: u64,
+    pub solxr_minted: u64,
+    pub sol_in_treasury: u64,
+    pub nav: u64,
+}
+
+#[event]
+pub struct SolxrMinted {
+    pub investor: Pubkey,
+    pub round_id: u64,
+    pub sol_amount: u64,
+    pub platform_fee: u64,
+    pub solxr_minted: u64,
+    pub premium: u64,
+    /// Price of the first Solxr of the purchase on the round curve
+    pub price: u64,
+    pub round_solxr_minted: u64,
+    pub nav: u64,
+}
+
+#[event]
+pub struct RoundOpened {
+    pub governance_authority: Pubkey,
+    pub round_id: u64,
+    pub premium: u64,
+    pub curve: PriceCurve,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub solxr_available: u64,
+    pub max_mint_per_wallet: u64,
+    pub start: u64,
+    pub nav: u64,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub governance_authority: Pubkey,
+    pub round_id: u64,
+    pub solxr_minted: u64,
+    pub solxr_available: u64,
+}
+
+#[event]
+pub struct BondIssued {
+    pub governance_authority: Pubkey,
+    pub bond_id: u64,
+    pub maturity: u64,
+    pub strike_price: u64,
+    pub supply: u64,
+    pub price: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub coupon_rate: u64,
+    pub coupon_interval: u64,
+    pub early_redemption_penalty: u64,
+    pub redemption_deadline: u64,
+    pub conversion_premium: Option<u64>,
+    pub issuance_nav: u64,
+    pub auction: Option<DutchAuction>,
+    pub allowlist_root: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct BondPurchased {
+    pub buyer: Pubkey,
+    pub bond_id: u64,
+    /// First edition number of the purchase, the following ones are consecutive
+    pub edition_number: u64,
+    pub quantity: u64,
+    pub price: u64,
+    /// Sol moved from the treasury to back the coupons of the purchased editions
+    pub coupon_reserve: u64,
+    pub sol_from_bond: u64,
+}
+
+#[event]
+pub struct CouponClaimed {
+    pub holder: Pubkey,
+    pub bond_id: u64,
+    pub edition_number: u64,
+    pub periods: u64,
+    pub sol_amount: u64,
+    pub sol_from_bond: u64,
+}
+
+#[event]
+pub struct BondConverted {
+    pub holder: Pubkey,
+    pub bond_id: u64,
+    pub edition_number: u64,
+    pub sol_amount: u64,
+    pub strike_price: u64,
+    pub solxr_minted: u64,
+    pub nav: u64,
+}
+
+#[event]
+pub struct BondRedeemed {
+    pub holder: Pubkey,
+    pub bond_id: u64,
+    pub edition_number: u64,
+    pub sol_returned: u64,
+    pub sol_from_bond: u64,
+}
+
+#[event]
+pub struct BondRedeemedEarly {
+    pub holder: Pubkey,
+    pub bond_id: u64,
+    pub edition_number: u64,
+    pub sol_returned: u64,
+    pub penalty: u64,
+    /// Accrued coupons paid along with the principal
+    pub coupon_paid: u64,
+    pub sol_in_treasury: u64,
+    pub sol_from_bond: u64,
+}
+
+#[event]
+pub struct WhitelistIssued {
+    pub governance_authority: Pubkey,
+    pub whitelist_id: u64,
+    pub price: u64,
+    pub maturity: u64,
+    pub expiration: u64,
+    pub supply: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub escrow_on_conversion: bool,
+    pub auction: Option<DutchAuction>,
+    pub allowlist_root: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct WhitelistPurchased {
+    pub buyer: Pubkey,
+    pub whitelist_id: u64,
+    /// First edition number of the purchase, the following ones are consecutive
+    pub edition_number: u64,
+    pub quantity: u64,
+    pub price: u64,
+    pub sol_in_treasury: u64,
+}
+
+#[event]
+pub struct WhitelistConverted {
+    pub holder: Pubkey,
+    pub whitelist_id: u64,
+    pub edition_number: u64,
+    /// The edition was moved to the SolStrategy escrow rather than burned
+    pub escrowed: bool,
+    pub solxr_minted: u64,
+    pub nav: u64,
+}
+
+#[event]
+pub struct StakeCreated {
+    pub governance_authority: Pubkey,
+    pub stake_id: u64,
+    pub stake_account: Pubkey,
+    pub lamports: u64,
+    pub sol_in_treasury: u64,
+    pub staked_lamports: u64,
+}
+
+#[event]
+pub struct StakeDelegated {
+    pub governance_authority: Pubkey,
+    pub stake_id: u64,
+    pub vote_account: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct StakeDeactivated {
+    pub governance_authority: Pubkey,
+    pub stake_id: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub governance_authority: Pubkey,
+    pub stake_id: u64,
+    pub lamports: u64,
+    /// Lamports withdrawn above the ones last counted in `staked_lamports`
+    pub rewards: u64,
+    pub sol_in_treasury: u64,
+    pub staked_lamports: u64,
+}
+
+#[event]
+pub struct StakeSynced {
+    pub stake_id: u64,
+    pub lamports: u64,
+    pub staked_lamports: u64,
+}
+
+#[event]
+pub struct StakePoolUpdated {
+    pub old_stake_pool: Option<Pubkey>,
+    pub new_stake_pool: Option<Pubkey>,
+}
+
+#[event]
+pub struct StakePoolDeposited {
+    pub governance_authority: Pubkey,
+    pub stake_pool: Pubkey,
+    pub lamports: u64,
+    pub pool_tokens: u64,
+    pub sol_in_treasury: u64,
+    pub stake_pool_lamports: u64,
+}
+
+#[event]
+pub struct StakePoolWithdrawn {
+    pub governance_authority: Pubkey,
+    pub stake_pool: Pubkey,
+    pub pool_tokens: u64,
+    pub lamports: u64,
+    pub sol_in_treasury: u64,
+    pub stake_pool_lamports: u64,
+}
+
+#[event]
+pub struct StakePoolSynced {
+    pub stake_pool: Pubkey,
+    pub pool_tokens: u64,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub stake_pool_lamports: u64,
+}
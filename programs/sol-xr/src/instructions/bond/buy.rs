@@ -1,32 +1,30 @@
 use {
     crate::{
+        events::BondPurchased,
         state::{
-            bonds::{Bond, BondRecord},
+            bonds::{Bond, BondEditionRecord, BondRecord},
             sol_strategy::SolStrategy,
         },
+        utils::{
+            edition::{
+                create_pda_account, MasterEditionAccounts, PrintEditionAccounts,
+                PRINT_EDITION_ACCOUNTS,
+            },
+            invariants,
+            merkle::{check_allowlist, AllowlistProof},
+        },
     },
     anchor_lang::prelude::*,
     anchor_lang::system_program,
     anchor_spl::{
         associated_token::AssociatedToken,
-        metadata::{
-            mpl_token_metadata::{
-                accounts::MasterEdition,
-                instructions::{
-                    MintNewEditionFromMasterEditionViaTokenCpi,
-                    MintNewEditionFromMasterEditionViaTokenCpiAccounts,
-                    MintNewEditionFromMasterEditionViaTokenInstructionArgs,
-                },
-                types::{MintNewEditionFromMasterEditionViaTokenArgs},
-            },
-            Metadata,
-        },
-        token::{mint_to, Mint, MintTo, Token, TokenAccount},
+        metadata::{mpl_token_metadata::accounts::MasterEdition, Metadata},
+        token::{Mint, Token, TokenAccount},
     },
 };
 
 #[derive(Accounts)]
-#[instruction(id: u64)]
+#[instruction(id: u64, quantity: u64)]
 pub struct BuyBond<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
@@ -61,6 +59,15 @@ pub struct BuyBond<'info> {
     )]
     pub bond_record: Account<'info, BondRecord>,
 
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + BondEditionRecord::INIT_SPACE,
+        seeds = [BondEditionRecord::SEED_PREFIX, bond.key().as_ref(), bond.next_edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_edition_record: Box<Account<'info, BondEditionRecord>>,
+
     #[account(
         init,
         payer = buyer,
@@ -139,7 +146,21 @@ pub struct BuyBond<'info> {
 }
 
 impl<'info> BuyBond<'info> {
-    pub fn handler(&mut self, bumps: &BuyBondBumps, _id: u64) -> Result<()> {
+    /// Mints `quantity` editions, the first through the named accounts and every additional one
+    /// through `PRINT_EDITION_ACCOUNTS` remaining accounts, followed by their edition records
+    pub fn handler(
+        &mut self,
+        bumps: &BuyBondBumps,
+        remaining_accounts: &[AccountInfo<'info>],
+        id: u64,
+        quantity: u64,
+        allowlist: Option<AllowlistProof>,
+    ) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_BONDS),
+            Error::ProtocolPaused
+        );
+
         let sol_strategy = &mut self.sol_strategy;
         let bond = &mut self.bond;
         let buyer = &mut self.buyer;
@@ -154,11 +175,35 @@ impl<'info> BuyBond<'info> {
             Error::MintingEnded
         );
 
+        require!(quantity > 0, Error::InvalidQuantity);
         let bond_record = &mut self.bond_record;
         require!(
-            bond_record.minted < bond.max_mint_per_wallet,
+            bond_record.minted + quantity <= bond.max_mint_per_wallet,
             Error::MaxMintPerWalletReached
         );
+        if let Some(allocation) =
+            check_allowlist(bond.allowlist_root, &buyer.key(), allowlist.as_ref())?
+        {
+            require!(
+                bond_record.minted + quantity <= allocation,
+                Error::ExceedsAllocation
+            );
+        }
+        let additional_count = quantity as usize - 1;
+        require!(
+            remaining_accounts.len() == additional_count * (PRINT_EDITION_ACCOUNTS + 1),
+            Error::EditionRecordAccountsMismatch
+        );
+        let (print_edition_accounts, edition_record_accounts) =
+            remaining_accounts.split_at(additional_count * PRINT_EDITION_ACCOUNTS);
+        let additional_editions = PrintEditionAccounts::from_remaining_accounts(
+            print_edition_accounts,
+            additional_count,
+        )?;
+        let price = bond.current_price(clock.unix_timestamp as u64);
+        let total_price = price
+            .checked_mul(quantity)
+            .ok_or(Error::EditionOverflow)?;
 
         system_program::transfer(
             CpiContext::new(
@@ -168,7 +213,7 @@ impl<'info> BuyBond<'info> {
                     to: self.treasury.to_account_info(),
                 },
             ),
-            bond.price,
+            total_price,
         )?;
 
         let current_supply: u64;
@@ -188,65 +233,127 @@ impl<'info> BuyBond<'info> {
                 .ok_or(Error::EditionOverflow)?;
 
             if let Some(max_supply) = master_edition_account.max_supply {
-                require!(current_supply < max_supply, Error::MaxSupplyReached); // todo: test supply
+                require!(current_supply + quantity <= max_supply, Error::MaxSupplyReached); // todo: test supply
             }
         }
         let sol_strategy_bump = bumps.sol_strategy;
         let sol_strategy_seeds: &[&[u8]] = &[SolStrategy::SEED_PREFIX, &[sol_strategy_bump]];
         let signer_seeds: &[&[&[u8]]] = &[&sol_strategy_seeds[..]];
-        let rent = &self.rent.to_account_info();
-
-        let mint_to_cpi_accounts = MintTo {
-            mint: self.buyer_bond_nft.to_account_info(),
-            to: self.buyer_token_account.to_account_info(),
-            authority: buyer.to_account_info(),
-        };
-        let mint_to_cpi_ctx =
-            CpiContext::new(self.token_program.to_account_info(), mint_to_cpi_accounts);
-        mint_to(mint_to_cpi_ctx, 1)?;
-
-        let cpi_accounts = MintNewEditionFromMasterEditionViaTokenCpiAccounts {
-            new_metadata: &self.buyer_metadata.to_account_info(),
-            new_edition: &self.buyer_edition.to_account_info(),
+        let master_edition_accounts = MasterEditionAccounts {
+            buyer: &buyer.to_account_info(),
+            owner: &sol_strategy.to_account_info(),
+            master_token_account: &self.bond_token_account.to_account_info(),
+            master_metadata: &self.bond_metadata.to_account_info(),
             master_edition: &self.bond_edition.to_account_info(),
-            new_mint: &self.buyer_bond_nft.to_account_info(),
-            edition_mark_pda: &self.edition_mark_pda.to_account_info(),
-            new_mint_authority: &buyer.to_account_info(),
-            payer: &buyer.to_account_info(),
-            token_account_owner: &sol_strategy.to_account_info(),
-            token_account: &self.bond_token_account.to_account_info(),
-            new_metadata_update_authority: &buyer.to_account_info(),
-            metadata: &self.bond_metadata.to_account_info(),
+            metadata_program: &self.metadata_program.to_account_info(),
             token_program: &self.token_program.to_account_info(),
+            associated_token_program: &self.associated_token_program.to_account_info(),
             system_program: &self.system_program.to_account_info(),
-            rent: Some(rent),
+            rent: &self.rent.to_account_info(),
         };
 
-        let instruction_args = MintNewEditionFromMasterEditionViaTokenInstructionArgs {
-            mint_new_edition_from_master_edition_via_token_args:
-                MintNewEditionFromMasterEditionViaTokenArgs {
-                    edition: next_edition_number,
-                },
-        };
+        master_edition_accounts.print(
+            &PrintEditionAccounts {
+                mint: &self.buyer_bond_nft.to_account_info(),
+                token_account: &self.buyer_token_account.to_account_info(),
+                metadata: &self.buyer_metadata.to_account_info(),
+                edition: &self.buyer_edition.to_account_info(),
+                edition_mark_pda: &self.edition_mark_pda.to_account_info(),
+            },
+            next_edition_number,
+            signer_seeds,
+        )?;
+        let elapsed_periods = bond.coupon_periods_at(clock.unix_timestamp as u64);
+        self.bond_edition_record.set_inner(BondEditionRecord::new(
+            bond.key(),
+            next_edition_number,
+            price,
+            elapsed_periods,
+        ));
+        for (index, (print_edition, edition_record)) in additional_editions
+            .iter()
+            .zip(edition_record_accounts)
+            .enumerate()
+        {
+            let edition_number = next_edition_number + 1 + index as u64;
+            master_edition_accounts.create_and_print(
+                print_edition,
+                bond.key(),
+                edition_number,
+                signer_seeds,
+            )?;
+            Self::create_edition_record(
+                master_edition_accounts.buyer,
+                master_edition_accounts.system_program,
+                edition_record,
+                BondEditionRecord::new(bond.key(), edition_number, price, elapsed_periods),
+            )?;
+        }
+        let last_edition_number = next_edition_number + quantity - 1;
 
-        MintNewEditionFromMasterEditionViaTokenCpi::new(
-            &self.metadata_program.to_account_info(),
-            cpi_accounts,
-            instruction_args,
-        )
-        .invoke_signed(signer_seeds)?;
+        bond_record.collection = bond.key();
+        bond_record.user = buyer.key();
+        bond_record.minted += quantity;
+        bond.next_edition_number = last_edition_number + 1;
+        bond.next_edition_marker = (last_edition_number + 1).checked_div(248).ok_or(Error::EditionOverflow)?.to_string();
+        // Coupons are reserved upfront so the treasury can't spend what is owed to holders
+        let coupon_reserve = bond.coupon_liability_at(price, clock.unix_timestamp as u64) * quantity;
+        require!(
+            sol_strategy.sol_in_treasury >= coupon_reserve,
+            Error::InsufficientTreasuryForCoupons
+        );
+        sol_strategy.sol_in_treasury -= coupon_reserve;
+        bond.sol_reserved += total_price + coupon_reserve;
+        bond.reserve_updates += 1;
+        sol_strategy.sol_from_bond += total_price + coupon_reserve;
+        sol_strategy.sol_from_bond_updates += 1;
 
-        self.bond_record.set_inner(BondRecord {
-            collection: bond.key(),
-            user: buyer.key(),
-            minted: 1,
+        emit!(BondPurchased {
+            buyer: buyer.key(),
+            bond_id: id,
+            edition_number: next_edition_number,
+            quantity,
+            price,
+            coupon_reserve,
+            sol_from_bond: sol_strategy.sol_from_bond,
         });
-        bond.next_edition_number = next_edition_number + 1;
-        bond.next_edition_marker = (next_edition_number + 1).checked_div(248).ok_or(Error::EditionOverflow)?.to_string();
-        sol_strategy.sol_from_bond += bond.price;
 
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
         Ok(())
     }
+
+    /// Creates the `BondEditionRecord` PDA of an additional edition passed as a remaining account
+    fn create_edition_record(
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        edition_record: &AccountInfo<'info>,
+        record: BondEditionRecord,
+    ) -> Result<()> {
+        let edition_bytes = record.edition_number.to_le_bytes();
+        let (address, bump) = Pubkey::find_program_address(
+            &[BondEditionRecord::SEED_PREFIX, record.bond.as_ref(), &edition_bytes],
+            &crate::ID,
+        );
+        require_keys_eq!(edition_record.key(), address, Error::InvalidEditionRecord);
+
+        let record_seeds: &[&[u8]] = &[
+            BondEditionRecord::SEED_PREFIX,
+            record.bond.as_ref(),
+            &edition_bytes,
+            &[bump],
+        ];
+        create_pda_account(
+            payer,
+            system_program,
+            edition_record,
+            8 + BondEditionRecord::INIT_SPACE,
+            &crate::ID,
+            &[record_seeds],
+        )?;
+
+        let mut data = edition_record.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])
+    }
 }
 
 #[error_code]
@@ -263,4 +370,16 @@ enum Error {
     EditionOverflow,
     #[msg("Master edition max supply reached.")]
     MaxSupplyReached,
+    #[msg("The treasury can't reserve the coupons of the purchased editions.")]
+    InsufficientTreasuryForCoupons,
+    #[msg("The quantity to mint must be greater than zero.")]
+    InvalidQuantity,
+    #[msg("Each additional edition needs its print accounts and its edition record.")]
+    EditionRecordAccountsMismatch,
+    #[msg("The edition record doesn't match the bond and edition number.")]
+    InvalidEditionRecord,
+    #[msg("The purchase exceeds the allocation of the buyer in the allowlist.")]
+    ExceedsAllocation,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
 }
@@ -0,0 +1,107 @@
+use {
+    crate::{
+        events::{Invariant, InvariantViolated},
+        state::{
+            bonds::{Bond, BondEditionRecord, BondReserveAudit},
+            sol_strategy::SolStrategy,
+        },
+        utils::invariants,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CheckBondReserves<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        seeds = [Bond::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub bond: Account<'info, Bond>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + BondReserveAudit::INIT_SPACE,
+        seeds = [BondReserveAudit::SEED_PREFIX, bond.key().as_ref()],
+        bump
+    )]
+    pub bond_reserve_audit: Account<'info, BondReserveAudit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CheckBondReserves<'info> {
+    /// Counts the reserve of the edition records passed as remaining accounts, in edition order
+    /// from where the previous call stopped. Once the last edition is counted, the total must
+    /// match the Sol reserved by the bond. The audit starts over whenever the bond reserve
+    /// changes in between. Anyone can call it, a violation is handled like `check_invariants`.
+    pub fn handler(
+        &mut self,
+        _bumps: &CheckBondReservesBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        _id: u64,
+        pause_on_violation: bool,
+    ) -> Result<()> {
+        let bond = &self.bond;
+        let audit = &mut self.bond_reserve_audit;
+        if audit.next_edition_number == 0 || audit.reserve_updates != bond.reserve_updates {
+            audit.reserve_updates = bond.reserve_updates;
+            audit.next_edition_number = 1;
+            audit.outstanding = 0;
+        }
+
+        for account in remaining_accounts {
+            require!(
+                audit.next_edition_number < bond.next_edition_number,
+                Error::TooManyEditionRecords
+            );
+            let (record_address, _) = Pubkey::find_program_address(
+                &[
+                    BondEditionRecord::SEED_PREFIX,
+                    bond.key().as_ref(),
+                    &audit.next_edition_number.to_le_bytes(),
+                ],
+                &crate::ID,
+            );
+            require_keys_eq!(account.key(), record_address, Error::InvalidEditionRecord);
+            let record = Account::<BondEditionRecord>::try_from(account)?;
+            audit.outstanding += record.reserve(bond);
+            audit.next_edition_number += 1;
+        }
+        if audit.next_edition_number < bond.next_edition_number {
+            return Ok(());
+        }
+
+        let mut violations = Vec::new();
+        if audit.outstanding != bond.sol_reserved {
+            violations.push(InvariantViolated {
+                invariant: Invariant::BondReserves,
+                expected: bond.sol_reserved,
+                actual: audit.outstanding,
+            });
+        }
+        // the next call starts a new audit
+        audit.next_edition_number = 0;
+        invariants::report_violations(&mut self.sol_strategy, violations, pause_on_violation)
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The edition record doesn't match the bond and the next edition to count.")]
+    InvalidEditionRecord,
+
+    #[msg("Every edition of the bond has already been counted.")]
+    TooManyEditionRecords,
+}
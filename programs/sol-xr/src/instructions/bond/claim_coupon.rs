@@ -0,0 +1,132 @@
+use {
+    crate::{
+        events::CouponClaimed,
+        state::{
+            bonds::{Bond, BondEditionRecord},
+            sol_strategy::SolStrategy,
+        },
+        utils::invariants,
+    },
+    anchor_lang::prelude::*,
+    anchor_lang::system_program,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64, edition_number: u64)]
+pub struct ClaimCoupon<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Bond::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub bond: Account<'info, Bond>,
+
+    #[account(
+        mut,
+        seeds = [BondEditionRecord::SEED_PREFIX, bond.key().as_ref(), edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_edition_record: Account<'info, BondEditionRecord>,
+
+    #[account(
+        seeds = [bond.key().as_ref(), edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub holder_bond_nft: Account<'info, Mint>,
+
+    #[account(
+        associated_token::mint = holder_bond_nft,
+        associated_token::authority = holder,
+        constraint = associated_nft_account.amount == 1 @ Error::InvalidTokenAmount
+    )]
+    pub associated_nft_account: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimCoupon<'info> {
+    pub fn handler(
+        &mut self,
+        bumps: &ClaimCouponBumps,
+        id: u64,
+        edition_number: u64,
+    ) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_BONDS),
+            Error::ProtocolPaused
+        );
+
+        let bond = &mut self.bond;
+        let bond_edition_record = &mut self.bond_edition_record;
+
+        let current_time = Clock::get()?.unix_timestamp as u64;
+        let periods = bond
+            .coupon_periods_at(current_time)
+            .saturating_sub(bond_edition_record.claimed_coupon_periods);
+        require!(periods > 0, Error::NoCouponDue);
+        let sol_amount = bond.coupon_amount(bond_edition_record.paid_price) * periods;
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.holder.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            sol_amount,
+        )?;
+
+        bond_edition_record.claimed_coupon_periods += periods;
+        bond.sol_reserved -= sol_amount;
+        bond.reserve_updates += 1;
+        self.sol_strategy.sol_from_bond -= sol_amount;
+        self.sol_strategy.sol_from_bond_updates += 1;
+
+        emit!(CouponClaimed {
+            holder: self.holder.key(),
+            bond_id: id,
+            edition_number,
+            periods,
+            sol_amount,
+            sol_from_bond: self.sol_strategy.sol_from_bond,
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The token account must contain exactly 1 NFT.")]
+    InvalidTokenAmount,
+    #[msg("No coupon is due for this edition.")]
+    NoCouponDue,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
+}
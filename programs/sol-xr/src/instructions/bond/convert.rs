@@ -1,5 +1,12 @@
 use {
-    crate::state::{bonds::Bond, sol_strategy::SolStrategy},
+    crate::{
+        events::{BondConverted, BondRedeemed, CouponClaimed},
+        state::{
+            bonds::{Bond, BondEditionRecord, BondOutcome},
+            sol_strategy::SolStrategy,
+        },
+        utils::invariants,
+    },
     anchor_lang::prelude::*,
     anchor_lang::system_program,
     anchor_spl::{
@@ -34,6 +41,13 @@ pub struct ConvertBond<'info> {
     )]
     pub bond: Account<'info, Bond>,
 
+    #[account(
+        mut,
+        seeds = [BondEditionRecord::SEED_PREFIX, bond.key().as_ref(), edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_edition_record: Box<Account<'info, BondEditionRecord>>,
+
     #[account(
         mut,
         seeds = [bond.key().as_ref(), edition_number.to_le_bytes().as_ref()],
@@ -73,15 +87,45 @@ impl<'info> ConvertBond<'info> {
     pub fn handler(
         &mut self,
         bumps: &ConvertBondBumps,
-        _id: u64,
-        _edition_number: u64,
+        id: u64,
+        edition_number: u64,
         convert: bool,
+        min_out: u64,
     ) -> Result<()> {
+        let pause_flag = if convert {
+            SolStrategy::PAUSE_CONVERSIONS
+        } else {
+            SolStrategy::PAUSE_REDEMPTIONS
+        };
+        require!(
+            !self.sol_strategy.is_paused(pause_flag),
+            Error::ProtocolPaused
+        );
+
         let bond = &self.bond;
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp as u64;
 
         require!(current_time >= bond.maturity, Error::BondNotMatured);
+        require!(
+            !convert || current_time <= bond.redemption_deadline,
+            Error::ConversionWindowClosed
+        );
+        require!(
+            !self.bond_edition_record.converted,
+            Error::EditionAlreadyConverted
+        );
+
+        self.pay_unclaimed_coupons(bumps, id, edition_number)?;
+        let price = self.bond_edition_record.paid_price;
+        let strike_price = self.bond.strike_price;
+        // Solxr minted when converting, Sol returned when redeeming
+        let amount_out = if convert {
+            Self::calculate_solxr_to_mint(price, strike_price)
+        } else {
+            price
+        };
+        require!(amount_out >= min_out, Error::SlippageExceeded);
 
         let burn_cpi_accounts = Burn {
             mint: self.buyer_bond_nft.to_account_info(),
@@ -93,7 +137,7 @@ impl<'info> ConvertBond<'info> {
 
 
         if convert {
-            let solxr_to_mint = Self::calculate_solxr_to_mint(bond.price, bond.strike_price);
+            let solxr_to_mint = amount_out;
 
             let sol_strategy_bump = bumps.sol_strategy;
             let sol_strategy_seeds: &[&[u8]] = &[SolStrategy::SEED_PREFIX, &[sol_strategy_bump]];
@@ -112,8 +156,21 @@ impl<'info> ConvertBond<'info> {
                 solxr_to_mint,
             )?;
 
-            self.sol_strategy.sol_from_bond -= bond.price;
-            self.sol_strategy.sol_in_treasury += bond.price;
+            self.bond.sol_reserved -= price;
+            self.sol_strategy.sol_from_bond -= price;
+            self.sol_strategy.sol_in_treasury += price;
+            self.sol_strategy.solxr_issued += solxr_to_mint;
+
+            self.token.reload()?;
+            emit!(BondConverted {
+                holder: self.buyer.key(),
+                bond_id: id,
+                edition_number,
+                sol_amount: price,
+                strike_price,
+                solxr_minted: solxr_to_mint,
+                nav: self.sol_strategy.nav(self.token.supply),
+            });
         } else {
             let sol_strategy_bump = bumps.treasury;
             let sol_strategy_seeds: &[&[u8]] = &[b"treasury", &[sol_strategy_bump]];
@@ -128,12 +185,79 @@ impl<'info> ConvertBond<'info> {
                     },
                     signer_seeds,
                 ),
-                bond.price,
+                price,
             )?;
 
-            self.sol_strategy.sol_from_bond -= bond.price;
+            self.bond.sol_reserved -= price;
+            self.sol_strategy.sol_from_bond -= price;
+
+            emit!(BondRedeemed {
+                holder: self.buyer.key(),
+                bond_id: id,
+                edition_number,
+                sol_returned: price,
+                sol_from_bond: self.sol_strategy.sol_from_bond,
+            });
         }
 
+        let outcome = if convert {
+            BondOutcome::Solxr
+        } else {
+            BondOutcome::Sol
+        };
+        self.bond_edition_record
+            .record_outcome(outcome, self.buyer.key(), current_time);
+        self.bond.reserve_updates += 1;
+        self.sol_strategy.sol_from_bond_updates += 1;
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        invariants::check_solxr_supply(&self.sol_strategy, self.token.supply)?;
+        Ok(())
+    }
+
+    /// Pays the coupons the holder hasn't claimed yet, the edition is burned right after
+    fn pay_unclaimed_coupons(
+        &mut self,
+        bumps: &ConvertBondBumps,
+        id: u64,
+        edition_number: u64,
+    ) -> Result<()> {
+        let bond = &self.bond;
+        let bond_edition_record = &mut self.bond_edition_record;
+
+        let periods = bond
+            .coupon_periods_at(bond.maturity)
+            .saturating_sub(bond_edition_record.claimed_coupon_periods);
+        if periods == 0 {
+            return Ok(());
+        }
+        let sol_amount = bond.coupon_amount(bond_edition_record.paid_price) * periods;
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.buyer.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            sol_amount,
+        )?;
+
+        bond_edition_record.claimed_coupon_periods += periods;
+        self.bond.sol_reserved -= sol_amount;
+        self.sol_strategy.sol_from_bond -= sol_amount;
+
+        emit!(CouponClaimed {
+            holder: self.buyer.key(),
+            bond_id: id,
+            edition_number,
+            periods,
+            sol_amount,
+            sol_from_bond: self.sol_strategy.sol_from_bond,
+        });
         Ok(())
     }
 
@@ -149,4 +273,12 @@ enum Error {
     BondNotMatured,
     #[msg("The token account must contain exactly 1 NFT.")]
     InvalidTokenAmount,
+    #[msg("The redemption deadline has passed, the bond can only be redeemed for Sol.")]
+    ConversionWindowClosed,
+    #[msg("The bond edition has already been converted or redeemed.")]
+    EditionAlreadyConverted,
+    #[msg("The Solxr minted or Sol returned is below the minimum accepted by the holder.")]
+    SlippageExceeded,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
 }
@@ -1,3 +1,7 @@
 pub mod sell;
 pub mod buy;
-pub mod convert;
\ No newline at end of file
+pub mod convert;
+pub mod claim_coupon;
+pub mod redeem_early;
+pub mod status;
+pub mod check_reserves;
\ No newline at end of file
@@ -0,0 +1,168 @@
+use {
+    crate::{
+        events::BondRedeemedEarly,
+        state::{
+            bonds::{Bond, BondEditionRecord, BondOutcome},
+            sol_strategy::SolStrategy,
+        },
+        utils::invariants,
+    },
+    anchor_lang::prelude::*,
+    anchor_lang::system_program,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{burn, Burn, Mint, Token, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64, edition_number: u64)]
+pub struct RedeemBondEarly<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Bond::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub bond: Account<'info, Bond>,
+
+    #[account(
+        mut,
+        seeds = [BondEditionRecord::SEED_PREFIX, bond.key().as_ref(), edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_edition_record: Box<Account<'info, BondEditionRecord>>,
+
+    #[account(
+        mut,
+        seeds = [bond.key().as_ref(), edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub buyer_bond_nft: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = buyer_bond_nft,
+        associated_token::authority = buyer,
+        constraint = associated_nft_account.amount == 1 @ Error::InvalidTokenAmount
+    )]
+    pub associated_nft_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedeemBondEarly<'info> {
+    pub fn handler(
+        &mut self,
+        bumps: &RedeemBondEarlyBumps,
+        id: u64,
+        edition_number: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_REDEMPTIONS),
+            Error::ProtocolPaused
+        );
+
+        let bond = &mut self.bond;
+        let current_time = Clock::get()?.unix_timestamp as u64;
+        require!(current_time < bond.maturity, Error::BondMatured);
+        require!(
+            !self.bond_edition_record.converted,
+            Error::EditionAlreadyConverted
+        );
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.buyer_bond_nft.to_account_info(),
+                    from: self.associated_nft_account.to_account_info(),
+                    authority: self.buyer.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        // Accrued coupons are paid out, the reserve of the future ones goes back to the treasury
+        let bond_edition_record = &mut self.bond_edition_record;
+        let paid_price = bond_edition_record.paid_price;
+        let elapsed_periods = bond.coupon_periods_at(current_time);
+        let total_periods = bond.coupon_periods_at(bond.maturity);
+        let coupon_paid = bond.coupon_amount(paid_price)
+            * elapsed_periods.saturating_sub(bond_edition_record.claimed_coupon_periods);
+        let released_coupons = bond.coupon_amount(paid_price) * (total_periods - elapsed_periods);
+        bond_edition_record.claimed_coupon_periods = total_periods;
+        bond_edition_record.record_outcome(BondOutcome::EarlySol, self.buyer.key(), current_time);
+
+        let penalty = bond.early_redemption_penalty_at(paid_price, current_time);
+        let sol_returned = paid_price - penalty;
+        require!(sol_returned >= min_sol_out, Error::SlippageExceeded);
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.buyer.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            sol_returned + coupon_paid,
+        )?;
+
+        let sol_strategy = &mut self.sol_strategy;
+        bond.sol_reserved -= paid_price + coupon_paid + released_coupons;
+        bond.reserve_updates += 1;
+        sol_strategy.sol_from_bond -= paid_price + coupon_paid + released_coupons;
+        sol_strategy.sol_from_bond_updates += 1;
+        sol_strategy.sol_in_treasury += penalty + released_coupons;
+
+        emit!(BondRedeemedEarly {
+            holder: self.buyer.key(),
+            bond_id: id,
+            edition_number,
+            sol_returned,
+            penalty,
+            coupon_paid,
+            sol_in_treasury: sol_strategy.sol_in_treasury,
+            sol_from_bond: sol_strategy.sol_from_bond,
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The bond has matured, use the maturity conversion or redemption instead.")]
+    BondMatured,
+    #[msg("The token account must contain exactly 1 NFT.")]
+    InvalidTokenAmount,
+    #[msg("The bond edition has already been converted or redeemed.")]
+    EditionAlreadyConverted,
+    #[msg("The Sol returned is below the minimum accepted by the holder.")]
+    SlippageExceeded,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
+}
@@ -1,5 +1,8 @@
 use {
-    crate::state::{bonds::Bond, sol_strategy::SolStrategy},
+    crate::{
+        events::BondIssued,
+        state::{auction::DutchAuction, bonds::Bond, sol_strategy::SolStrategy},
+    },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
@@ -77,6 +80,12 @@ pub struct SellBond<'info> {
     )]
     pub bond_edition: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [b"token"],
+        bump
+    )]
+    pub token: Account<'info, Mint>,
+
     pub metadata_program: Program<'info, Metadata>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -98,15 +107,59 @@ impl<'info> SellBond<'info> {
         max_mint_per_wallet: u64,
         start_time: u64,
         end_time: u64,
+        coupon_rate: u64,
+        coupon_interval: u64,
+        early_redemption_penalty: u64,
+        redemption_deadline: u64,
+        conversion_premium: Option<u64>,
+        auction: Option<DutchAuction>,
+        allowlist_root: Option<[u8; 32]>,
     ) -> Result<()> {
+        require!(
+            redemption_deadline >= maturity,
+            Error::RedemptionDeadlineBeforeMaturity
+        );
+        require!(
+            early_redemption_penalty <= u64::pow(10, 9),
+            Error::InvalidEarlyRedemptionPenalty
+        );
+        if let Some(auction) = &auction {
+            require!(auction.is_valid(start_time, end_time), Error::InvalidAuction);
+        }
+        if coupon_rate > 0 {
+            require!(
+                coupon_interval > 0 && maturity > start_time,
+                Error::InvalidCouponSchedule
+            );
+        }
+
+        let (strike_price, issuance_nav) = match conversion_premium {
+            Some(premium) => {
+                let nav = self.sol_strategy.nav(self.token.supply);
+                require!(nav > 0, Error::NavIsZero);
+                (Self::calculate_strike_price(nav, premium)?, nav)
+            }
+            None => (strike_price, 0),
+        };
+
         self.bond.set_inner(Bond {
             maturity,
             strike_price,
+            conversion_premium,
+            issuance_nav,
             supply,
             price,
             max_mint_per_wallet,
             start_time,
             end_time,
+            auction,
+            allowlist_root,
+            coupon_rate,
+            coupon_interval,
+            early_redemption_penalty,
+            redemption_deadline,
+            sol_reserved: 0,
+            reserve_updates: 0,
             next_edition_number: 1u64,
             next_edition_marker: (1 / 248).to_string(),
         });
@@ -185,14 +238,54 @@ impl<'info> SellBond<'info> {
         );
         master_edition_account.invoke_signed(mint_auth_signer)?;
 
+        emit!(BondIssued {
+            governance_authority: self.governance_authority.key(),
+            bond_id: self.sol_strategy.next_bond_id,
+            maturity,
+            strike_price,
+            supply,
+            price,
+            start_time,
+            end_time,
+            coupon_rate,
+            coupon_interval,
+            early_redemption_penalty,
+            redemption_deadline,
+            conversion_premium,
+            issuance_nav,
+            auction,
+            allowlist_root,
+        });
+
         self.sol_strategy.next_bond_id += 1;
 
         Ok(())
     }
+
+    /// Strike price of `nav * (1 + conversion_premium)`, the premium being scaled so 1_000_000_000 = 100%
+    fn calculate_strike_price(nav: u64, conversion_premium: u64) -> Result<u64> {
+        let strike_price = (nav as u128)
+            .checked_mul(u128::pow(10, 9) + conversion_premium as u128)
+            .ok_or(Error::StrikePriceOverflow)?
+            / u128::pow(10, 9);
+        u64::try_from(strike_price).map_err(|_| error!(Error::StrikePriceOverflow))
+    }
 }
 
 #[error_code]
 enum Error {
     #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
     UnauthorizedGovernanceAuthority,
+    #[msg("A coupon-bearing bond needs a coupon interval and a maturity after the start time.")]
+    InvalidCouponSchedule,
+    #[msg("The early redemption penalty cannot exceed 100%.")]
+    InvalidEarlyRedemptionPenalty,
+    #[msg("The redemption deadline cannot be before maturity.")]
+    RedemptionDeadlineBeforeMaturity,
+    #[msg("The strike price cannot be derived from NAV while there is no Solxr supply or Sol in the treasury.")]
+    NavIsZero,
+    #[msg("The strike price derived from NAV doesn't fit in a u64.")]
+    StrikePriceOverflow,
+    #[msg("The auction needs a floor price below the start price and a decay that fits the sale period.")]
+    InvalidAuction,
 }
@@ -0,0 +1,32 @@
+use {
+    crate::state::bonds::{Bond, BondEditionRecord},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64, edition_number: u64)]
+pub struct GetBondEditionStatus<'info> {
+    #[account(
+        seeds = [Bond::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub bond: Account<'info, Bond>,
+
+    #[account(
+        seeds = [BondEditionRecord::SEED_PREFIX, bond.key().as_ref(), edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_edition_record: Account<'info, BondEditionRecord>,
+}
+
+impl<'info> GetBondEditionStatus<'info> {
+    /// Returns the record created when the edition was bought
+    pub fn handler(
+        &self,
+        _bumps: &GetBondEditionStatusBumps,
+        _id: u64,
+        _edition_number: u64,
+    ) -> Result<BondEditionRecord> {
+        Ok((*self.bond_edition_record).clone())
+    }
+}
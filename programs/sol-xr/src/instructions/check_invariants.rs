@@ -0,0 +1,46 @@
+use {
+    crate::{state::sol_strategy::SolStrategy, utils::invariants},
+    anchor_lang::prelude::*,
+    anchor_spl::token::Mint,
+};
+
+#[derive(Accounts)]
+pub struct CheckInvariants<'info> {
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"token"],
+        bump
+    )]
+    pub token: Account<'info, Mint>,
+}
+
+impl<'info> CheckInvariants<'info> {
+    /// Checks the treasury and Solxr supply invariants, the bond reserves are checked by
+    /// `check_sol_from_bond` and `check_bond_reserves`. Anyone can call it. A violation fails the transaction, unless
+    /// `pause_on_violation` is set, then the whole protocol is paused instead.
+    pub fn handler(&mut self, _bumps: &CheckInvariantsBumps, pause_on_violation: bool) -> Result<()> {
+        let mut violations = Vec::new();
+        if let Some(violation) =
+            invariants::treasury_coverage(&self.sol_strategy, self.treasury.lamports())?
+        {
+            violations.push(violation);
+        }
+        if let Some(violation) = invariants::solxr_supply(&self.sol_strategy, self.token.supply) {
+            violations.push(violation);
+        }
+
+        invariants::report_violations(&mut self.sol_strategy, violations, pause_on_violation)
+    }
+}
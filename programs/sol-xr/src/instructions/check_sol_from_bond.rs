@@ -0,0 +1,97 @@
+use {
+    crate::{
+        events::{Invariant, InvariantViolated},
+        state::{
+            bonds::{Bond, SolFromBondAudit},
+            sol_strategy::SolStrategy,
+        },
+        utils::invariants,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CheckSolFromBond<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SolFromBondAudit::INIT_SPACE,
+        seeds = [SolFromBondAudit::SEED_PREFIX],
+        bump
+    )]
+    pub sol_from_bond_audit: Account<'info, SolFromBondAudit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CheckSolFromBond<'info> {
+    /// Sums the Sol reserved by the bonds passed as remaining accounts, in id order from where
+    /// the previous call stopped. Once the last bond is counted, the total must match
+    /// `sol_from_bond`. The audit starts over whenever `sol_from_bond` changes in between.
+    /// Anyone can call it, a violation is handled like `check_invariants`.
+    pub fn handler(
+        &mut self,
+        _bumps: &CheckSolFromBondBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        pause_on_violation: bool,
+    ) -> Result<()> {
+        let sol_strategy = &self.sol_strategy;
+        let audit = &mut self.sol_from_bond_audit;
+        if audit.next_bond_id == 0
+            || audit.sol_from_bond_updates != sol_strategy.sol_from_bond_updates
+        {
+            audit.sol_from_bond_updates = sol_strategy.sol_from_bond_updates;
+            audit.next_bond_id = 1;
+            audit.sol_reserved = 0;
+        }
+
+        for account in remaining_accounts {
+            require!(
+                audit.next_bond_id < sol_strategy.next_bond_id,
+                Error::TooManyBondAccounts
+            );
+            let (bond_address, _) = Pubkey::find_program_address(
+                &[Bond::SEED_PREFIX, &audit.next_bond_id.to_le_bytes()],
+                &crate::ID,
+            );
+            require_keys_eq!(account.key(), bond_address, Error::InvalidBondAccount);
+            let bond = Account::<Bond>::try_from(account)?;
+            audit.sol_reserved += bond.sol_reserved;
+            audit.next_bond_id += 1;
+        }
+        if audit.next_bond_id < sol_strategy.next_bond_id {
+            return Ok(());
+        }
+
+        let mut violations = Vec::new();
+        if audit.sol_reserved != sol_strategy.sol_from_bond {
+            violations.push(InvariantViolated {
+                invariant: Invariant::SolFromBond,
+                expected: sol_strategy.sol_from_bond,
+                actual: audit.sol_reserved,
+            });
+        }
+        // the next call starts a new audit
+        audit.next_bond_id = 0;
+        invariants::report_violations(&mut self.sol_strategy, violations, pause_on_violation)
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The bond account doesn't match the next bond id to count.")]
+    InvalidBondAccount,
+
+    #[msg("Every bond has already been counted.")]
+    TooManyBondAccounts,
+}
@@ -0,0 +1,40 @@
+use {
+    crate::{events::GovernanceAuthorityAccepted, state::sol_strategy::SolStrategy},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct AcceptGovernanceAuthority<'info> {
+    #[account(
+        constraint = sol_strategy.pending_governance_authority == Some(pending_governance_authority.key()) @ Error::UnauthorizedPendingGovernanceAuthority,
+    )]
+    pub pending_governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+}
+
+impl<'info> AcceptGovernanceAuthority<'info> {
+    pub fn handler(&mut self, _bumps: &AcceptGovernanceAuthorityBumps) -> Result<()> {
+        let old_governance_authority = self.sol_strategy.governance_authority;
+
+        self.sol_strategy.governance_authority = self.pending_governance_authority.key();
+        self.sol_strategy.pending_governance_authority = None;
+
+        emit!(GovernanceAuthorityAccepted {
+            old_governance_authority,
+            new_governance_authority: self.sol_strategy.governance_authority,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the pending governance authority defined in the SolStrategy.")]
+    UnauthorizedPendingGovernanceAuthority,
+}
@@ -0,0 +1,56 @@
+use {
+    crate::{
+        events::GovernanceActionCancelled,
+        state::{governance_action::GovernanceAction, sol_strategy::SolStrategy},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CancelGovernanceAction<'info> {
+    #[account(
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [GovernanceAction::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub governance_action: Account<'info, GovernanceAction>,
+
+    #[account(
+        mut,
+        address = governance_action.proposer @ Error::InvalidProposer,
+    )]
+    pub proposer: SystemAccount<'info>,
+}
+
+impl<'info> CancelGovernanceAction<'info> {
+    /// Drops a queued action that has not been executed yet
+    pub fn handler(&mut self, _bumps: &CancelGovernanceActionBumps, id: u64) -> Result<()> {
+        emit!(GovernanceActionCancelled {
+            governance_authority: self.governance_authority.key(),
+            action_id: id,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The proposer doesn't match the one recorded in the governance action.")]
+    InvalidProposer,
+}
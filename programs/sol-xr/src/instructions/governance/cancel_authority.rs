@@ -0,0 +1,45 @@
+use {
+    crate::{events::GovernanceAuthorityProposalCancelled, state::sol_strategy::SolStrategy},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CancelGovernanceAuthority<'info> {
+    #[account(
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = sol_strategy.pending_governance_authority.is_some() @ Error::NoPendingGovernanceAuthority,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+}
+
+impl<'info> CancelGovernanceAuthority<'info> {
+    pub fn handler(&mut self, _bumps: &CancelGovernanceAuthorityBumps) -> Result<()> {
+        let cancelled_governance_authority = self
+            .sol_strategy
+            .pending_governance_authority
+            .take()
+            .ok_or(Error::NoPendingGovernanceAuthority)?;
+
+        emit!(GovernanceAuthorityProposalCancelled {
+            governance_authority: self.sol_strategy.governance_authority,
+            cancelled_governance_authority,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("There is no pending governance authority proposal to cancel.")]
+    NoPendingGovernanceAuthority,
+}
@@ -0,0 +1,142 @@
+use {
+    crate::{
+        events::GovernanceActionExecuted,
+        state::{
+            governance_action::{GovernanceAction, TimelockedAction},
+            price_observation::PriceObservation,
+            sol_strategy::SolStrategy,
+        },
+        utils::stake_pool::StakePoolState,
+        GOVERNANCE_GRACE_PERIOD,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount},
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ExecuteGovernanceAction<'info> {
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [GovernanceAction::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub governance_action: Account<'info, GovernanceAction>,
+
+    #[account(
+        mut,
+        address = governance_action.proposer @ Error::InvalidProposer,
+    )]
+    pub proposer: SystemAccount<'info>,
+
+    /// Required to execute `ConfigureTwap`
+    #[account(
+        mut,
+        seeds = [PriceObservation::SEED_PREFIX],
+        bump
+    )]
+    pub price_observation: Option<Box<Account<'info, PriceObservation>>>,
+
+    /// CHECK: Stake pool configured in the SolStrategy, parsed by `StakePoolState`.
+    /// Required to execute `SetStakePool` while a stake pool is set.
+    #[account(
+        constraint = Some(stake_pool.key()) == sol_strategy.stake_pool @ Error::InvalidStakePool,
+    )]
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Treasury pool token account of the configured stake pool, checked in the handler
+    /// as it doesn't exist until the first deposit
+    pub pool_token_account: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> ExecuteGovernanceAction<'info> {
+    /// Applies a queued action once its eta is reached. Anyone can call it.
+    pub fn handler(&mut self, _bumps: &ExecuteGovernanceActionBumps, id: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp as u64;
+        let governance_action = &self.governance_action;
+        require!(
+            now >= governance_action.eta,
+            Error::GovernanceActionNotReady
+        );
+        require!(
+            now <= governance_action.eta + GOVERNANCE_GRACE_PERIOD,
+            Error::GovernanceActionExpired
+        );
+
+        // The SolStrategy may have changed since the action was queued
+        governance_action.action.validate(&self.sol_strategy)?;
+        if let TimelockedAction::SetStakePool { .. } = governance_action.action {
+            self.check_stake_pool_emptied()?;
+        }
+        governance_action.action.apply(
+            &mut self.sol_strategy,
+            self.price_observation.as_deref_mut().map(|account| &mut **account),
+        )?;
+
+        emit!(GovernanceActionExecuted {
+            action_id: id,
+            action: governance_action.action.clone(),
+        });
+        Ok(())
+    }
+
+    /// The stake pool can only change once the treasury holds none of its pool tokens. The
+    /// balance is read from the pool token account, `stake_pool_lamports` may not be synced.
+    fn check_stake_pool_emptied(&self) -> Result<()> {
+        if self.sol_strategy.stake_pool.is_none() {
+            return Ok(());
+        }
+        let (Some(stake_pool), Some(pool_token_account)) =
+            (&self.stake_pool, &self.pool_token_account)
+        else {
+            return err!(Error::StakePoolAccountsRequired);
+        };
+
+        let pool = StakePoolState::load(stake_pool)?;
+        let (treasury, _) = Pubkey::find_program_address(&[b"treasury"], &crate::ID);
+        require_keys_eq!(
+            pool_token_account.key(),
+            get_associated_token_address(&treasury, &pool.pool_mint),
+            Error::InvalidPoolTokenAccount
+        );
+        if pool_token_account.data_is_empty() {
+            return Ok(());
+        }
+        let pool_tokens =
+            TokenAccount::try_deserialize(&mut &pool_token_account.try_borrow_data()?[..])?.amount;
+        require!(pool_tokens == 0, Error::StakePoolInUse);
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The proposer doesn't match the one recorded in the governance action.")]
+    InvalidProposer,
+
+    #[msg("The governance action eta has not been reached yet.")]
+    GovernanceActionNotReady,
+
+    #[msg("The governance action grace period is over, it can only be cancelled.")]
+    GovernanceActionExpired,
+
+    #[msg("The stake pool doesn't match the one configured in the SolStrategy.")]
+    InvalidStakePool,
+
+    #[msg("The stake pool and the treasury pool token account are required.")]
+    StakePoolAccountsRequired,
+
+    #[msg("The pool token account isn't the treasury account of the stake pool mint.")]
+    InvalidPoolTokenAccount,
+
+    #[msg("The treasury still holds tokens of the current stake pool.")]
+    StakePoolInUse,
+}
@@ -0,0 +1,7 @@
+pub mod queue_action;
+pub mod execute_action;
+pub mod cancel_action;
+pub mod accept_authority;
+pub mod cancel_authority;
+pub mod set_pause;
+pub mod pause;
\ No newline at end of file
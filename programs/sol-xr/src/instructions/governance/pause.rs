@@ -0,0 +1,50 @@
+use {
+    crate::{events::PauseUpdated, state::sol_strategy::SolStrategy},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        constraint = sol_strategy.guardian == Some(authority.key())
+            || sol_strategy.governance_authority == authority.key() @ Error::UnauthorizedPauseAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+}
+
+impl<'info> Pause<'info> {
+    /// Pauses the given subsystems on top of the ones already paused, unpausing goes through `set_pause`
+    pub fn handler(&mut self, _bumps: &PauseBumps, flags: u8) -> Result<()> {
+        require!(
+            flags != 0 && flags & !SolStrategy::PAUSE_ALL == 0,
+            Error::InvalidPauseFlags
+        );
+
+        let paused = self.sol_strategy.paused | flags;
+        emit!(PauseUpdated {
+            authority: self.authority.key(),
+            old_paused: self.sol_strategy.paused,
+            new_paused: paused,
+        });
+        self.sol_strategy.paused = paused;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg(
+        "Caller is neither the guardian nor the governance authority defined in the SolStrategy."
+    )]
+    UnauthorizedPauseAuthority,
+
+    #[msg("The pause flags are empty or contain unknown bits.")]
+    InvalidPauseFlags,
+}
@@ -0,0 +1,83 @@
+use {
+    crate::{
+        events::GovernanceActionQueued,
+        state::{
+            governance_action::{GovernanceAction, TimelockedAction},
+            sol_strategy::SolStrategy,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct QueueGovernanceAction<'info> {
+    #[account(
+        mut,
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + GovernanceAction::INIT_SPACE,
+        seeds = [GovernanceAction::SEED_PREFIX, sol_strategy.next_governance_action_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub governance_action: Account<'info, GovernanceAction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QueueGovernanceAction<'info> {
+    /// Queues a sensitive action, anyone can execute it from `eta` which must be at least the
+    /// governance delay away
+    pub fn handler(
+        &mut self,
+        _bumps: &QueueGovernanceActionBumps,
+        action: TimelockedAction,
+        eta: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp as u64;
+        let sol_strategy = &mut self.sol_strategy;
+        require!(
+            eta >= now + sol_strategy.governance_delay,
+            Error::EtaBeforeGovernanceDelay
+        );
+        action.validate(sol_strategy)?;
+
+        let id = sol_strategy.next_governance_action_id;
+        sol_strategy.next_governance_action_id += 1;
+
+        self.governance_action.set_inner(GovernanceAction {
+            id,
+            proposer: self.governance_authority.key(),
+            action: action.clone(),
+            eta,
+        });
+
+        emit!(GovernanceActionQueued {
+            governance_authority: self.governance_authority.key(),
+            action_id: id,
+            action,
+            eta,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The action eta must be at least the governance delay away.")]
+    EtaBeforeGovernanceDelay,
+}
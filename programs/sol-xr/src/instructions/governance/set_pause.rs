@@ -0,0 +1,45 @@
+use {
+    crate::{events::PauseUpdated, state::sol_strategy::SolStrategy},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+}
+
+impl<'info> SetPause<'info> {
+    pub fn handler(&mut self, _bumps: &SetPauseBumps, paused: u8) -> Result<()> {
+        require!(
+            paused & !SolStrategy::PAUSE_ALL == 0,
+            Error::InvalidPauseFlags
+        );
+
+        emit!(PauseUpdated {
+            authority: self.governance_authority.key(),
+            old_paused: self.sol_strategy.paused,
+            new_paused: paused,
+        });
+        self.sol_strategy.paused = paused;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The pause flags contain unknown bits.")]
+    InvalidPauseFlags,
+}
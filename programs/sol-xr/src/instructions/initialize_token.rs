@@ -1,10 +1,12 @@
 use {
     crate::{
-        state::sol_strategy::SolStrategy, DURATION,
-        GOVERNANCE_AUTHORITY, MAX_MINT_PER_WALLET, MAX_PLATFORM_MINT_FEE, MINTING_ROUNDS,
-        MIN_PREMIUM_NAV_RATIO, NAV_GROWTH_RATE, PLATFORM_ADDRESS, PLATFORM_MINT_FEE, SOLXR_DECIMAL,
+        events::StrategyInitialized,
+        state::{price_observation::PriceObservation, sol_strategy::SolStrategy}, DURATION,
+        GOVERNANCE_AUTHORITY, GOVERNANCE_DELAY, MAX_MINT_PER_WALLET, MAX_PLATFORM_MINT_FEE,
+        MINTING_ROUNDS, MIN_PREMIUM_NAV_RATIO, NAV_GROWTH_RATE, PLATFORM_ADDRESS,
+        PLATFORM_MINT_FEE, REDEMPTION_CAP_PER_EPOCH, REDEMPTION_FEE, SOLXR_DECIMAL,
     },
-    anchor_lang::prelude::*,
+    anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         metadata::{
             mpl_token_metadata::instructions::{
@@ -55,6 +57,23 @@ pub struct InitializeToken<'info> {
     /// CHECK: Validated by PDA derivation
     pub token_metadata: UncheckedAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// Created without a price source, the TWAP is configured through a governance action
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + PriceObservation::INIT_SPACE,
+        seeds = [PriceObservation::SEED_PREFIX],
+        bump
+    )]
+    pub price_observation: Box<Account<'info, PriceObservation>>,
+
     pub metadata_program: Program<'info, Metadata>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -70,11 +89,22 @@ impl<'info> InitializeToken<'info> {
     ) -> Result<()> {
         self.sol_strategy.set_inner(SolStrategy {
             initial_pool_cap,
+            initial_pool_invested: 0,
             individual_address_cap,
             sol_in_treasury: 0,
             sol_from_bond: 0,
+            sol_from_bond_updates: 0,
+            solxr_issued: 0,
+            staked_lamports: 0,
+            stake_pool: None,
+            stake_pool_lamports: 0,
             governance_authority: self.governance_authority.key(), // todo update to official controlled governance address
+            pending_governance_authority: None,
+            governance_delay: GOVERNANCE_DELAY,
+            guardian: None,
+            paused: 0,
             platform_address: PLATFORM_ADDRESS,
+            platform_fee_splits: vec![],
             allow_new_mint: false,
             platform_mint_fee: PLATFORM_MINT_FEE,
             max_platform_mint_fee: MAX_PLATFORM_MINT_FEE,
@@ -84,8 +114,20 @@ impl<'info> InitializeToken<'info> {
             minting_rounds: MINTING_ROUNDS,
             next_minting_rounds: 1,
             mint_duration: DURATION,
+            price_oracle: None,
+            oracle_max_staleness: 0,
+            oracle_max_confidence: 0,
+            oracle_max_deviation: 0,
+            twap_window: 0,
+            allow_redemption: false,
+            redemption_fee: REDEMPTION_FEE,
+            redemption_cap_per_epoch: REDEMPTION_CAP_PER_EPOCH,
+            redemption_epoch: 0,
+            redeemed_in_epoch: 0,
             next_bond_id: 1,
             next_whitelist_id: 1,
+            next_stake_id: 1,
+            next_governance_action_id: 1,
         });
 
         let token_metadata = &self.token_metadata.to_account_info();
@@ -128,6 +170,28 @@ impl<'info> InitializeToken<'info> {
         );
         token_metadata_account.invoke_signed(mint_auth_signer)?;
 
+        // The treasury holds its own rent exempt minimum, every other lamport is backed by a counter
+        let treasury_rent = Rent::get()?
+            .minimum_balance(0)
+            .saturating_sub(self.treasury.lamports());
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.governance_authority.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                },
+            ),
+            treasury_rent,
+        )?;
+
+        emit!(StrategyInitialized {
+            governance_authority: self.governance_authority.key(),
+            token: self.token.key(),
+            initial_pool_cap,
+            individual_address_cap,
+        });
+
         Ok(())
     }
 }
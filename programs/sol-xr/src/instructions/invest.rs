@@ -1,5 +1,5 @@
 use {
-    crate::state::sol_strategy::SolStrategy,
+    crate::{events::Invested, state::sol_strategy::SolStrategy, utils::invariants},
     anchor_lang::prelude::Rent,
     anchor_lang::prelude::*,
     anchor_lang::system_program,
@@ -32,7 +32,7 @@ pub struct Invest<'info> {
 
     #[account(
         mut,
-        constraint = amount + sol_strategy.sol_in_treasury <= sol_strategy.initial_pool_cap @ Error::InitialSolCapError,
+        constraint = amount + sol_strategy.initial_pool_invested <= sol_strategy.initial_pool_cap @ Error::InitialSolCapError,
         constraint = amount + associated_token_account.amount <= sol_strategy.individual_address_cap @ Error::ATACapError,
         seeds = [SolStrategy::SEED_PREFIX],
         bump
@@ -53,6 +53,11 @@ pub struct Invest<'info> {
 
 impl<'info> Invest<'info> {
     pub fn handler(&mut self, bumps: &InvestBumps, amount: u64) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_INVEST),
+            Error::ProtocolPaused
+        );
+
         // Get the bump for the mint authority PDA
         let mint_auth_bump = bumps.sol_strategy;
         let mint_auth_seeds: &[&[u8]] = &[SolStrategy::SEED_PREFIX, &[mint_auth_bump]];
@@ -85,15 +90,31 @@ impl<'info> Invest<'info> {
         )?;
 
         self.sol_strategy.sol_in_treasury += amount;
+        self.sol_strategy.initial_pool_invested += amount;
+        self.sol_strategy.solxr_issued += amount;
+
+        self.token.reload()?;
+        emit!(Invested {
+            investor: self.investor.key(),
+            sol_amount: amount,
+            solxr_minted: amount,
+            sol_in_treasury: self.sol_strategy.sol_in_treasury,
+            nav: self.sol_strategy.nav(self.token.supply),
+        });
 
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        invariants::check_solxr_supply(&self.sol_strategy, self.token.supply)?;
         Ok(())
     }
 }
 
 #[error_code]
+#[allow(clippy::enum_variant_names)]
 enum Error {
     #[msg("The amount would cause the ATA balance to exceed the individual address cap.")]
     ATACapError,
     #[msg("The amount would cause the program PDA to exceed the initial pool cap.")]
     InitialSolCapError,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
 }
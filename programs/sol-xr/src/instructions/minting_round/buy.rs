@@ -1,7 +1,13 @@
 use {
     crate::{
+        events::SolxrMinted,
         mint_round::{AssociatedRoundAccount, MintRound},
         state::sol_strategy::SolStrategy,
+        utils::{
+            invariants,
+            merkle::{check_allowlist, AllowlistProof},
+        },
+        BASIS_POINTS,
     },
     anchor_lang::prelude::Rent,
     anchor_lang::prelude::*,
@@ -52,7 +58,7 @@ pub struct BuySolxr<'info> {
         mut,
         constraint = sol_strategy.allow_new_mint == true @ Error::MintingNotAllowed, // check if new mint is open
         constraint = sol_strategy.next_minting_rounds == id @ Error::InvalidMintingRound, // check if new mint is open
-        constraint = Self::calculate_solxr_to_mint(amount, mint_round.premium) + mint_round.solxr_minted <= mint_round.solxr_available @ Error::ExceedsAvailableSolxr, // check if amount won't go over available mint
+        constraint = mint_round.calculate_solxr_to_mint(amount, Clock::get()?.unix_timestamp as u64, sol_strategy.mint_duration) + mint_round.solxr_minted <= mint_round.solxr_available @ Error::ExceedsAvailableSolxr, // check if amount won't go over available mint
         constraint = amount + associated_round_account.amount_minted <= mint_round.max_mint_per_wallet @ Error::ExceedsMaxMintPerWallet, // check if user doesn't mint more than max
         constraint = Clock::get()?.unix_timestamp as u64 - mint_round.start <= sol_strategy.mint_duration  @ Error::MintingDurationEnded, // check if duration of minting is not over
         seeds = [MintRound::SEED_PREFIX,&id.to_le_bytes()],
@@ -82,14 +88,76 @@ pub struct BuySolxr<'info> {
 }
 
 impl<'info> BuySolxr<'info> {
-    pub fn handler(&mut self, bumps: &BuySolxrBumps, _id: u64, amount: u64) -> Result<()> {
+    pub fn handler(
+        &mut self,
+        bumps: &BuySolxrBumps,
+        fee_recipients: &[AccountInfo<'info>],
+        id: u64,
+        amount: u64,
+        min_solxr_out: u64,
+        allowlist: Option<AllowlistProof>,
+    ) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_MINT_ROUNDS),
+            Error::ProtocolPaused
+        );
+        // the allocation is in lamport, like `max_mint_per_wallet`
+        if let Some(allocation) = check_allowlist(
+            self.mint_round.allowlist_root,
+            &self.investor.key(),
+            allowlist.as_ref(),
+        )? {
+            require!(
+                amount + self.associated_round_account.amount_minted <= allocation,
+                Error::ExceedsAllocation
+            );
+        }
+
         let mut platform_fee =
             Self::calculate_platform_fee(self.sol_strategy.platform_mint_fee, amount);
         if platform_fee > self.sol_strategy.max_platform_mint_fee {
             platform_fee = self.sol_strategy.max_platform_mint_fee;
         }
 
-        // Transfer SOL to platform designated account
+        let current_time = Clock::get()?.unix_timestamp as u64;
+        let mint_duration = self.sol_strategy.mint_duration;
+        let price = self.mint_round.spot_price(current_time, mint_duration);
+        let solxr_to_mint = self.mint_round.calculate_solxr_to_mint(
+            amount - platform_fee,
+            current_time,
+            mint_duration,
+        );
+        require!(solxr_to_mint >= min_solxr_out, Error::SlippageExceeded);
+
+        // Transfer each split of the fee to its recipient, passed in order as remaining accounts
+        let platform_fee_splits = &self.sol_strategy.platform_fee_splits;
+        require!(
+            fee_recipients.len() == platform_fee_splits.len(),
+            Error::InvalidFeeRecipients
+        );
+        let mut platform_share = platform_fee;
+        for (split, recipient) in platform_fee_splits.iter().zip(fee_recipients) {
+            require_keys_eq!(
+                recipient.key(),
+                split.recipient,
+                Error::InvalidFeeRecipients
+            );
+            let share = Self::calculate_fee_share(platform_fee, split.basis_points);
+            platform_share -= share;
+
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.investor.to_account_info(),
+                        to: recipient.clone(),
+                    },
+                ),
+                share,
+            )?;
+        }
+
+        // Transfer the remaining fee to platform designated account
         system_program::transfer(
             CpiContext::new(
                 self.system_program.to_account_info(),
@@ -98,7 +166,7 @@ impl<'info> BuySolxr<'info> {
                     to: self.platform_address.to_account_info(),
                 },
             ),
-            platform_fee,
+            platform_share,
         )?;
 
         // Transfer SOL to treasury
@@ -112,9 +180,6 @@ impl<'info> BuySolxr<'info> {
             ),
             amount.sub(platform_fee),
         )?;
-        let solxr_to_mint =
-            Self::calculate_solxr_to_mint(amount - platform_fee, self.mint_round.premium);
-
         // Mint token for payer
         // Get the bump for the mint authority PDA
         let mint_auth_bump = bumps.sol_strategy;
@@ -134,8 +199,26 @@ impl<'info> BuySolxr<'info> {
             solxr_to_mint,
         )?;
 
+        self.sol_strategy.sol_in_treasury += amount - platform_fee;
+        self.sol_strategy.solxr_issued += solxr_to_mint;
         self.mint_round.solxr_minted += solxr_to_mint;
         self.associated_round_account.amount_minted += amount;
+
+        self.token.reload()?;
+        emit!(SolxrMinted {
+            investor: self.investor.key(),
+            round_id: id,
+            sol_amount: amount,
+            platform_fee,
+            solxr_minted: solxr_to_mint,
+            premium: self.mint_round.premium,
+            price,
+            round_solxr_minted: self.mint_round.solxr_minted,
+            nav: self.sol_strategy.nav(self.token.supply),
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        invariants::check_solxr_supply(&self.sol_strategy, self.token.supply)?;
         Ok(())
     }
 
@@ -143,9 +226,9 @@ impl<'info> BuySolxr<'info> {
         let fee = platform_mint_fee as u128 * amount as u128 / u128::pow(10, 9);
         fee as u64
     }
-    fn calculate_solxr_to_mint(amount: u64, premium: u64) -> u64 {
-        let value = amount as u128 * u128::pow(10, 9) / premium as u128;
-        value as u64
+    fn calculate_fee_share(platform_fee: u64, basis_points: u16) -> u64 {
+        let share = platform_fee as u128 * basis_points as u128 / BASIS_POINTS as u128;
+        share as u64
     }
 }
 
@@ -163,4 +246,12 @@ enum Error {
     ExceedsAvailableSolxr,
     #[msg("Minting round duration has ended")]
     MintingDurationEnded,
+    #[msg("The remaining accounts don't match the platform fee recipients stored in the strategy")]
+    InvalidFeeRecipients,
+    #[msg("The Solxr minted is below the minimum accepted by the investor")]
+    SlippageExceeded,
+    #[msg("The purchase exceeds the allocation of the investor in the allowlist")]
+    ExceedsAllocation,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
 }
@@ -1,5 +1,6 @@
 use {
     crate::{
+        events::RoundClosed,
         mint_round::{MintRound},
         state::sol_strategy::SolStrategy,
     },
@@ -34,6 +35,13 @@ pub struct CloseMintingRound<'info> {
 
 impl<'info> CloseMintingRound<'info> {
     pub fn handler(&mut self, _bumps: &CloseMintingRoundBumps) -> Result<()> {
+        emit!(RoundClosed {
+            governance_authority: self.governance_authority.key(),
+            round_id: self.sol_strategy.next_minting_rounds,
+            solxr_minted: self.mint_round.solxr_minted,
+            solxr_available: self.mint_round.solxr_available,
+        });
+
         self.sol_strategy.allow_new_mint = false;
         self.sol_strategy.next_minting_rounds += 1;
         Ok(())
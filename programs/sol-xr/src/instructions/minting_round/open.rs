@@ -1,7 +1,9 @@
 use {
     crate::{
-        mint_round::{MintRound},
-        state::sol_strategy::SolStrategy,
+        events::RoundOpened,
+        mint_round::{MintRound, PriceCurve},
+        state::{price_observation::PriceObservation, sol_strategy::SolStrategy},
+        utils::oracle::PriceFeed,
     },
     anchor_lang::prelude::Rent,
     anchor_lang::prelude::*,
@@ -47,6 +49,15 @@ pub struct OpenMintingRound<'info> {
     )]
     pub token: Account<'info, Mint>,
 
+    /// CHECK: Pyth-style price account, must match the oracle stored in the strategy and is parsed by `PriceFeed`
+    #[account(
+        constraint = Some(price_oracle.key()) == sol_strategy.price_oracle @ Error::InvalidPriceOracle,
+    )]
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+
+    /// Only one PriceObservation account can exist, created with the token at its PDA
+    pub price_observation: Option<Box<Account<'info, PriceObservation>>>,
+
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -56,13 +67,18 @@ impl<'info> OpenMintingRound<'info> {
     pub fn handler(
         &mut self,
         _bumps: &OpenMintingRoundBumps,
-        _id: u64,
+        id: u64,
         market_value: u64,
+        curve: PriceCurve,
+        allowlist_root: Option<[u8; 32]>,
     ) -> Result<()> {
+        let market_value = self.resolve_market_value(market_value)?;
+        let reference_value = self.resolve_reference_value(market_value)?;
+
         let pass_mint_value_requirement = Self::check_mint_value_requirement(
-            market_value as u128,
+            reference_value as u128,
             self.sol_strategy.min_premium_nav_ratio as u128,
-            self.sol_strategy.sol_in_treasury as u128,
+            self.sol_strategy.sol_backing() as u128,
             self.token.supply as u128,
         );
         require!(
@@ -70,28 +86,106 @@ impl<'info> OpenMintingRound<'info> {
             Error::MarketValueBelowMinPremium
         );
 
+        match curve {
+            PriceCurve::Fixed => {}
+            PriceCurve::Bonding { max_premium } => {
+                require!(max_premium >= market_value, Error::InvalidPriceCurve);
+            }
+            PriceCurve::TimeDecay { floor_premium } => {
+                require!(floor_premium <= market_value, Error::InvalidPriceCurve);
+                // the round never sells below the minimum premium, whatever the time left
+                require!(
+                    Self::check_mint_value_requirement(
+                        floor_premium as u128,
+                        self.sol_strategy.min_premium_nav_ratio as u128,
+                        self.sol_strategy.sol_backing() as u128,
+                        self.token.supply as u128,
+                    ),
+                    Error::MarketValueBelowMinPremium
+                );
+            }
+        }
+
         self.sol_strategy.allow_new_mint = true;
 
         self.mint_round.premium = market_value;
+        self.mint_round.curve = curve;
         self.mint_round.max_mint_per_wallet = self.sol_strategy.max_mint_per_wallet;
+        self.mint_round.allowlist_root = allowlist_root;
         self.mint_round.solxr_minted = 0;
         self.mint_round.solxr_available = Self::calculate_solxr_to_mint(
             self.sol_strategy.nav_growth_rate as u128,
             self.token.supply as u128,
             market_value as u128,
-            self.sol_strategy.sol_in_treasury as u128,
+            self.sol_strategy.sol_backing() as u128,
         )?;
         self.mint_round.start = Clock::get()?.unix_timestamp as u64;
+
+        emit!(RoundOpened {
+            governance_authority: self.governance_authority.key(),
+            round_id: id,
+            premium: self.mint_round.premium,
+            curve,
+            allowlist_root,
+            solxr_available: self.mint_round.solxr_available,
+            max_mint_per_wallet: self.mint_round.max_mint_per_wallet,
+            start: self.mint_round.start,
+            nav: self.sol_strategy.nav(self.token.supply),
+        });
         Ok(())
     }
 
+    /// Checks the supplied market value against the oracle price when an oracle is configured.
+    /// A market value of zero is replaced by the oracle price.
+    fn resolve_market_value(&self, market_value: u64) -> Result<u64> {
+        let Some(price_oracle) = &self.price_oracle else {
+            require!(
+                self.sol_strategy.price_oracle.is_none(),
+                Error::PriceOracleRequired
+            );
+            return Ok(market_value);
+        };
+
+        let oracle_price = PriceFeed::load(&price_oracle.to_account_info())?.checked_price(
+            Clock::get()?.unix_timestamp,
+            self.sol_strategy.oracle_max_staleness,
+            self.sol_strategy.oracle_max_confidence,
+        )?;
+        if market_value == 0 {
+            return Ok(oracle_price);
+        }
+
+        let deviation = (market_value as u128).abs_diff(oracle_price as u128) * u128::pow(10, 9)
+            / oracle_price as u128;
+        require!(
+            deviation <= self.sol_strategy.oracle_max_deviation as u128,
+            Error::MarketValueDeviatesFromOracle
+        );
+        Ok(market_value)
+    }
+
+    /// Value checked against the minimum premium, the Solxr TWAP when a window is configured
+    fn resolve_reference_value(&self, market_value: u64) -> Result<u64> {
+        let twap_window = self.sol_strategy.twap_window;
+        if twap_window == 0 {
+            return Ok(market_value);
+        }
+        let Some(price_observation) = &self.price_observation else {
+            return err!(Error::PriceObservationRequired);
+        };
+
+        price_observation
+            .twap(Clock::get()?.unix_timestamp as u64, twap_window)
+            .ok_or(error!(Error::InsufficientPriceObservations))
+    }
+
     fn calculate_solxr_to_mint(
         nav_growth_rate: u128,
         solxr_supply: u128,
         market_value: u128,
-        sol_in_treasury_lamports: u128,
+        sol_backing_lamports: u128,
     ) -> Result<u64> {
-        let nav = sol_in_treasury_lamports * u128::pow(10, 9) / solxr_supply;
+        let nav = sol_backing_lamports * u128::pow(10, 9) / solxr_supply;
         require!(nav != 0, Error::NavIsZero);
 
         let market_value_ratio = market_value * u128::pow(10, 9) / nav;
@@ -117,10 +211,10 @@ impl<'info> OpenMintingRound<'info> {
     fn check_mint_value_requirement(
         market_value: u128,
         min_premium_nav_ratio: u128,
-        sol_in_treasury_lamports: u128,
+        sol_backing_lamports: u128,
         token_supply: u128,
     ) -> bool {
-        let nav = sol_in_treasury_lamports * u128::pow(10, 9) / token_supply;
+        let nav = sol_backing_lamports * u128::pow(10, 9) / token_supply;
 
         let min_required_value =
             (min_premium_nav_ratio + u128::pow(10, 9)) * nav / u128::pow(10, 9);
@@ -164,4 +258,22 @@ enum Error {
 
     #[msg("Calculation failed: Market value to NAV ratio must exceed (1 + NAV growth rate) for formula.")]
     MarketValueInsufficientForFormula,
+
+    #[msg("The provided price oracle doesn't match the one stored in the SolStrategy.")]
+    InvalidPriceOracle,
+
+    #[msg("A price oracle is configured in the SolStrategy but was not provided.")]
+    PriceOracleRequired,
+
+    #[msg("The provided market value deviates from the oracle price by more than the allowed band.")]
+    MarketValueDeviatesFromOracle,
+
+    #[msg("A TWAP window is configured in the SolStrategy but the price observation account was not provided.")]
+    PriceObservationRequired,
+
+    #[msg("The recorded price observations don't cover the TWAP window.")]
+    InsufficientPriceObservations,
+
+    #[msg("A bonding curve must end above the premium and a time decay must end below it.")]
+    InvalidPriceCurve,
 }
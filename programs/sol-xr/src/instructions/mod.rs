@@ -1,17 +1,48 @@
 pub mod initialize_token;
 pub mod invest;
+pub mod redeem;
+pub mod record_price;
+pub mod sync_treasury;
+pub mod check_invariants;
+pub mod check_sol_from_bond;
 pub mod minting_round;
 pub mod bond;
 pub mod whitelist;
+pub mod governance;
+pub mod staking;
 
 pub use initialize_token::*;
 pub use invest::*;
+pub use redeem::*;
+pub use record_price::*;
+pub use sync_treasury::*;
+pub use check_invariants::*;
+pub use check_sol_from_bond::*;
 pub use minting_round::open::*;
 pub use minting_round::close::*;
 pub use minting_round::buy::*;
 pub use bond::sell::*;
 pub use bond::buy::*;
 pub use bond::convert::*;
+pub use bond::claim_coupon::*;
+pub use bond::redeem_early::*;
+pub use bond::status::*;
+pub use bond::check_reserves::*;
 pub use whitelist::sell::*;
 pub use whitelist::buy::*;
-pub use whitelist::convert::*;
\ No newline at end of file
+pub use whitelist::convert::*;
+pub use staking::create::*;
+pub use staking::delegate::*;
+pub use staking::deactivate::*;
+pub use staking::withdraw::*;
+pub use staking::sync::*;
+pub use staking::deposit_pool::*;
+pub use staking::withdraw_pool::*;
+pub use staking::sync_pool::*;
+pub use governance::queue_action::*;
+pub use governance::execute_action::*;
+pub use governance::cancel_action::*;
+pub use governance::accept_authority::*;
+pub use governance::cancel_authority::*;
+pub use governance::set_pause::*;
+pub use governance::pause::*;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use {
+    crate::{
+        events::PriceObserved,
+        state::{price_observation::PriceObservation, sol_strategy::SolStrategy},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{spl_token::native_mint, Mint, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct RecordPriceObservation<'info> {
+    pub reporter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PriceObservation::SEED_PREFIX],
+        bump
+    )]
+    pub price_observation: Box<Account<'info, PriceObservation>>,
+
+    #[account(
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        seeds = [b"token"],
+        bump
+    )]
+    pub token: Account<'info, Mint>,
+
+    #[account(
+        constraint = Some(pool_solxr_vault.key()) == price_observation.pool_solxr_vault @ Error::InvalidPoolVault,
+        constraint = pool_solxr_vault.mint == token.key() @ Error::InvalidPoolVault,
+    )]
+    pub pool_solxr_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = Some(pool_sol_vault.key()) == price_observation.pool_sol_vault @ Error::InvalidPoolVault,
+        constraint = pool_sol_vault.mint == native_mint::ID @ Error::InvalidPoolVault,
+    )]
+    pub pool_sol_vault: Option<Account<'info, TokenAccount>>,
+}
+
+impl<'info> RecordPriceObservation<'info> {
+    pub fn handler(
+        &mut self,
+        _bumps: &RecordPriceObservationBumps,
+        price: Option<u64>,
+    ) -> Result<()> {
+        let price = match price {
+            // Authorized reporters push a price directly
+            Some(price) => {
+                require!(
+                    self.price_observation.price_reporter == Some(self.reporter.key()),
+                    Error::UnauthorizedPriceReporter
+                );
+                price
+            }
+            // Anyone can crank the spot price of the configured AMM pool
+            None => match (&self.pool_solxr_vault, &self.pool_sol_vault) {
+                (Some(pool_solxr_vault), Some(pool_sol_vault)) => {
+                    require!(pool_solxr_vault.amount > 0, Error::EmptyPool);
+                    Self::calculate_pool_price(pool_solxr_vault.amount, pool_sol_vault.amount)
+                }
+                _ => return err!(Error::MissingPoolVaults),
+            },
+        };
+        require!(price > 0, Error::InvalidPrice);
+
+        let timestamp = Clock::get()?.unix_timestamp as u64;
+        if let Some(latest) = self.price_observation.latest() {
+            require!(
+                timestamp >= latest.timestamp + self.price_observation.min_observation_interval,
+                Error::ObservationTooEarly
+            );
+        }
+        let price = self.price_observation.bounded_price(
+            timestamp,
+            self.sol_strategy.twap_window,
+            price,
+        );
+        self.price_observation.push(timestamp, price);
+
+        emit!(PriceObserved {
+            reporter: self.reporter.key(),
+            timestamp,
+            price,
+        });
+        Ok(())
+    }
+
+    fn calculate_pool_price(solxr_reserve: u64, sol_reserve: u64) -> u64 {
+        // Solxr and wrapped Sol share the same decimals
+        let price = sol_reserve as u128 * u128::pow(10, 9) / solxr_reserve as u128;
+        price as u64
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The provided pool vault doesn't match the one configured for the TWAP.")]
+    InvalidPoolVault,
+    #[msg("Caller is not the price reporter configured for the TWAP.")]
+    UnauthorizedPriceReporter,
+    #[msg("The configured pool vaults must be provided when no price is reported.")]
+    MissingPoolVaults,
+    #[msg("The pool holds no Solxr.")]
+    EmptyPool,
+    #[msg("The observed price must be greater than zero.")]
+    InvalidPrice,
+    #[msg("The minimum interval since the last observation has not passed.")]
+    ObservationTooEarly,
+}
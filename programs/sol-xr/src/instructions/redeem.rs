@@ -0,0 +1,168 @@
+use {
+    crate::{events::SolxrRedeemed, state::sol_strategy::SolStrategy, utils::invariants},
+    anchor_lang::prelude::*,
+    anchor_lang::system_program,
+    anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct RedeemSolxr<'info> {
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = token.supply > 0 @ Error::TokenSupplyIsZero,
+        seeds = [b"token"],
+        bump
+    )]
+    pub token: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token,
+        associated_token::authority = redeemer,
+        constraint = associated_token_account.amount >= amount @ Error::InsufficientSolxrBalance,
+    )]
+    pub associated_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = sol_strategy.allow_redemption @ Error::RedemptionNotAllowed,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedeemSolxr<'info> {
+    pub fn handler(
+        &mut self,
+        bumps: &RedeemSolxrBumps,
+        amount: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_REDEMPTIONS),
+            Error::ProtocolPaused
+        );
+
+        require!(amount > 0, Error::InvalidRedemptionAmount);
+
+        let sol_value = Self::calculate_sol_to_redeem(
+            amount,
+            self.sol_strategy.sol_backing(),
+            self.token.supply,
+        );
+        let redemption_fee =
+            Self::calculate_redemption_fee(self.sol_strategy.redemption_fee, sol_value);
+        let sol_to_return = sol_value - redemption_fee;
+        require!(sol_to_return > 0, Error::InvalidRedemptionAmount);
+        require!(sol_to_return >= min_sol_out, Error::SlippageExceeded);
+        // Staked Sol counts towards the value but only idle Sol can be paid out
+        require!(
+            sol_to_return <= self.sol_strategy.sol_in_treasury,
+            Error::InsufficientTreasuryLiquidity
+        );
+
+        // Reset the redemption counter when a new epoch starts
+        let epoch = Clock::get()?.epoch;
+        if epoch != self.sol_strategy.redemption_epoch {
+            self.sol_strategy.redemption_epoch = epoch;
+            self.sol_strategy.redeemed_in_epoch = 0;
+        }
+        require!(
+            self.sol_strategy.redeemed_in_epoch + sol_to_return
+                <= self.sol_strategy.redemption_cap_per_epoch,
+            Error::ExceedsRedemptionCap
+        );
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.token.to_account_info(),
+                    from: self.associated_token_account.to_account_info(),
+                    authority: self.redeemer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let treasury_bump = bumps.treasury;
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[treasury_bump]];
+        let signer_seeds: &[&[&[u8]]] = &[treasury_seeds];
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.redeemer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            sol_to_return,
+        )?;
+
+        // The redemption fee stays in the treasury and accrues to the remaining holders
+        self.sol_strategy.sol_in_treasury -= sol_to_return;
+        self.sol_strategy.redeemed_in_epoch += sol_to_return;
+        self.sol_strategy.solxr_issued -= amount;
+
+        self.token.reload()?;
+        emit!(SolxrRedeemed {
+            redeemer: self.redeemer.key(),
+            solxr_burned: amount,
+            sol_returned: sol_to_return,
+            redemption_fee,
+            sol_in_treasury: self.sol_strategy.sol_in_treasury,
+            token_supply: self.token.supply,
+            nav: self.sol_strategy.nav(self.token.supply),
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        invariants::check_solxr_supply(&self.sol_strategy, self.token.supply)?;
+        Ok(())
+    }
+
+    fn calculate_sol_to_redeem(amount: u64, sol_backing: u64, token_supply: u64) -> u64 {
+        let value = amount as u128 * sol_backing as u128 / token_supply as u128;
+        value as u64
+    }
+    fn calculate_redemption_fee(redemption_fee: u64, sol_value: u64) -> u64 {
+        let fee = redemption_fee as u128 * sol_value as u128 / u128::pow(10, 9);
+        fee as u64
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Redemption of Solxr is not allowed at this time.")]
+    RedemptionNotAllowed,
+    #[msg("Cannot redeem while the Solxr supply is zero.")]
+    TokenSupplyIsZero,
+    #[msg("The token account does not hold enough Solxr.")]
+    InsufficientSolxrBalance,
+    #[msg("The amount is too small to redeem any Sol.")]
+    InvalidRedemptionAmount,
+    #[msg("The amount would exceed the redemption cap for the current epoch.")]
+    ExceedsRedemptionCap,
+    #[msg("The Sol returned is below the minimum accepted by the holder.")]
+    SlippageExceeded,
+    #[msg("The treasury doesn't hold enough unstaked Sol to pay this redemption.")]
+    InsufficientTreasuryLiquidity,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
+}
@@ -0,0 +1,171 @@
+use {
+    crate::{
+        events::StakeCreated,
+        state::{native_stake::NativeStake, sol_strategy::SolStrategy},
+        utils::invariants,
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            program::invoke,
+            stake::{
+                self, instruction as stake_instruction,
+                state::{Authorized, Lockup, StakeStateV2},
+            },
+        },
+        system_program,
+    },
+};
+
+#[derive(Accounts)]
+pub struct CreateStake<'info> {
+    #[account(
+        mut,
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + NativeStake::INIT_SPACE,
+        seeds = [NativeStake::SEED_PREFIX, sol_strategy.next_stake_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub native_stake: Account<'info, NativeStake>,
+
+    /// CHECK: Created and initialized as a stake account by this instruction
+    #[account(
+        mut,
+        seeds = [NativeStake::STAKE_ACCOUNT_SEED_PREFIX, sol_strategy.next_stake_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateStake<'info> {
+    /// Funds a new stake account with `amount` lamports from the treasury, the treasury
+    /// stays its staker and withdrawer
+    pub fn handler(&mut self, bumps: &CreateStakeBumps, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.sol_strategy.sol_in_treasury,
+            Error::InsufficientTreasuryBalance
+        );
+        // the rent reserve stays locked in the stake account, the rest is delegated
+        let rent_reserve = Rent::get()?.minimum_balance(StakeStateV2::size_of());
+        require!(amount > rent_reserve, Error::StakeAmountTooLow);
+
+        let id = self.sol_strategy.next_stake_id;
+        let id_bytes = id.to_le_bytes();
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        let stake_account_seeds: &[&[u8]] = &[
+            NativeStake::STAKE_ACCOUNT_SEED_PREFIX,
+            &id_bytes,
+            &[bumps.stake_account],
+        ];
+
+        // The stake account address is predictable, so it may already hold lamports sent by
+        // anyone. Topping it up and allocating it works whether or not it was pre-funded.
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.stake_account.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            amount,
+        )?;
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Allocate {
+                    account_to_allocate: self.stake_account.to_account_info(),
+                },
+                &[stake_account_seeds],
+            ),
+            StakeStateV2::size_of() as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Assign {
+                    account_to_assign: self.stake_account.to_account_info(),
+                },
+                &[stake_account_seeds],
+            ),
+            &stake::program::ID,
+        )?;
+
+        let authorized = Authorized {
+            staker: self.treasury.key(),
+            withdrawer: self.treasury.key(),
+        };
+        invoke(
+            &stake_instruction::initialize(self.stake_account.key, &authorized, &Lockup::default()),
+            &[
+                self.stake_account.to_account_info(),
+                self.rent.to_account_info(),
+                self.stake_program.to_account_info(),
+            ],
+        )?;
+
+        self.native_stake.set_inner(NativeStake {
+            id,
+            stake_account: self.stake_account.key(),
+            vote_account: None,
+            lamports: amount,
+            deactivated: false,
+        });
+
+        let sol_strategy = &mut self.sol_strategy;
+        sol_strategy.sol_in_treasury -= amount;
+        sol_strategy.staked_lamports += amount;
+        sol_strategy.next_stake_id += 1;
+
+        emit!(StakeCreated {
+            governance_authority: self.governance_authority.key(),
+            stake_id: id,
+            stake_account: self.stake_account.key(),
+            lamports: amount,
+            sol_in_treasury: sol_strategy.sol_in_treasury,
+            staked_lamports: sol_strategy.staked_lamports,
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The treasury doesn't hold enough unstaked Sol to fund the stake account.")]
+    InsufficientTreasuryBalance,
+
+    #[msg("The amount doesn't cover the rent exempt reserve of a stake account.")]
+    StakeAmountTooLow,
+}
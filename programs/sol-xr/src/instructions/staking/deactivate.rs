@@ -0,0 +1,103 @@
+use {
+    crate::{
+        events::StakeDeactivated,
+        state::{native_stake::NativeStake, sol_strategy::SolStrategy},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            program::invoke_signed,
+            stake::{self, instruction as stake_instruction},
+        },
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct DeactivateStake<'info> {
+    #[account(
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        seeds = [NativeStake::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub native_stake: Account<'info, NativeStake>,
+
+    /// CHECK: Stake account created by `create_stake`, matched against the NativeStake
+    #[account(
+        mut,
+        address = native_stake.stake_account @ Error::InvalidStakeAccount,
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+impl<'info> DeactivateStake<'info> {
+    /// Starts the cooldown of a delegated stake, it can be withdrawn once inactive
+    pub fn handler(&mut self, bumps: &DeactivateStakeBumps, id: u64) -> Result<()> {
+        require!(
+            self.native_stake.vote_account.is_some(),
+            Error::StakeNotDelegated
+        );
+        require!(
+            !self.native_stake.deactivated,
+            Error::StakeAlreadyDeactivated
+        );
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        invoke_signed(
+            &stake_instruction::deactivate_stake(self.stake_account.key, self.treasury.key),
+            &[
+                self.stake_account.to_account_info(),
+                self.clock.to_account_info(),
+                self.treasury.to_account_info(),
+                self.stake_program.to_account_info(),
+            ],
+            &[treasury_seeds],
+        )?;
+
+        self.native_stake.deactivated = true;
+
+        emit!(StakeDeactivated {
+            governance_authority: self.governance_authority.key(),
+            stake_id: id,
+            epoch: self.clock.epoch,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The stake account doesn't match the one recorded for this stake id.")]
+    InvalidStakeAccount,
+
+    #[msg("Only a delegated stake can be deactivated.")]
+    StakeNotDelegated,
+
+    #[msg("This stake is already deactivated.")]
+    StakeAlreadyDeactivated,
+}
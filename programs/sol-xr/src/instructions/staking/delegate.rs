@@ -0,0 +1,110 @@
+use {
+    crate::{
+        events::StakeDelegated,
+        state::{native_stake::NativeStake, sol_strategy::SolStrategy},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            program::invoke_signed,
+            stake::{self, instruction as stake_instruction},
+            sysvar,
+        },
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct DelegateStake<'info> {
+    #[account(
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        seeds = [NativeStake::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub native_stake: Account<'info, NativeStake>,
+
+    /// CHECK: Stake account created by `create_stake`, matched against the NativeStake
+    #[account(
+        mut,
+        address = native_stake.stake_account @ Error::InvalidStakeAccount,
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake program
+    pub vote_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: Stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+    /// CHECK: Stake config account, still required by the stake program
+    #[account(address = stake::config::ID)]
+    pub stake_config: UncheckedAccount<'info>,
+    /// CHECK: Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+impl<'info> DelegateStake<'info> {
+    pub fn handler(&mut self, bumps: &DelegateStakeBumps, id: u64) -> Result<()> {
+        require!(!self.native_stake.deactivated, Error::StakeDeactivated);
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        invoke_signed(
+            &stake_instruction::delegate_stake(
+                self.stake_account.key,
+                self.treasury.key,
+                self.vote_account.key,
+            ),
+            &[
+                self.stake_account.to_account_info(),
+                self.vote_account.to_account_info(),
+                self.clock.to_account_info(),
+                self.stake_history.to_account_info(),
+                self.stake_config.to_account_info(),
+                self.treasury.to_account_info(),
+                self.stake_program.to_account_info(),
+            ],
+            &[treasury_seeds],
+        )?;
+
+        self.native_stake.vote_account = Some(self.vote_account.key());
+
+        emit!(StakeDelegated {
+            governance_authority: self.governance_authority.key(),
+            stake_id: id,
+            vote_account: self.vote_account.key(),
+            lamports: self.native_stake.lamports,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The stake account doesn't match the one recorded for this stake id.")]
+    InvalidStakeAccount,
+
+    #[msg("This stake was deactivated, it can only be withdrawn to the treasury.")]
+    StakeDeactivated,
+}
@@ -0,0 +1,169 @@
+use {
+    crate::{
+        events::StakePoolDeposited,
+        state::sol_strategy::SolStrategy,
+        utils::{
+            invariants,
+            stake_pool::{
+                self, StakePoolKeys, StakePoolState, STAKE_POOL_PROGRAM_ID,
+                WITHDRAW_AUTHORITY_SEED,
+            },
+        },
+    },
+    anchor_lang::{prelude::*, solana_program::program::invoke_signed},
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+pub struct DepositStakePool<'info> {
+    #[account(
+        mut,
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    /// CHECK: Stake pool configured in the SolStrategy, parsed by `StakePoolState`
+    #[account(
+        mut,
+        constraint = Some(stake_pool.key()) == sol_strategy.stake_pool @ Error::InvalidStakePool,
+    )]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by PDA derivation
+    #[account(
+        seeds = [stake_pool.key().as_ref(), WITHDRAW_AUTHORITY_SEED],
+        bump,
+        seeds::program = stake_pool_program.key(),
+    )]
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = governance_authority,
+        associated_token::mint = pool_mint,
+        associated_token::authority = treasury,
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: SPL stake pool program
+    #[account(address = STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositStakePool<'info> {
+    /// Deposits `lamports` from the treasury into the stake pool, the pool tokens are held
+    /// by the treasury
+    pub fn handler(
+        &mut self,
+        bumps: &DepositStakePoolBumps,
+        lamports: u64,
+        min_pool_tokens_out: u64,
+    ) -> Result<()> {
+        require!(lamports > 0, Error::InvalidDepositAmount);
+        require!(
+            lamports <= self.sol_strategy.sol_in_treasury,
+            Error::InsufficientTreasuryBalance
+        );
+        let pool = StakePoolState::load(&self.stake_pool)?;
+        require_keys_eq!(pool.pool_mint, self.pool_mint.key(), Error::InvalidPoolMint);
+
+        let keys = StakePoolKeys {
+            stake_pool: self.stake_pool.key(),
+            withdraw_authority: self.stake_pool_withdraw_authority.key(),
+            reserve_stake: self.reserve_stake.key(),
+            manager_fee_account: self.manager_fee_account.key(),
+            pool_mint: self.pool_mint.key(),
+        };
+        let pool_tokens_before = self.pool_token_account.amount;
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        invoke_signed(
+            &stake_pool::deposit_sol(
+                &keys,
+                self.treasury.key,
+                &self.pool_token_account.key(),
+                lamports,
+                min_pool_tokens_out,
+            ),
+            &[
+                self.stake_pool.to_account_info(),
+                self.stake_pool_withdraw_authority.to_account_info(),
+                self.reserve_stake.to_account_info(),
+                self.treasury.to_account_info(),
+                self.pool_token_account.to_account_info(),
+                self.manager_fee_account.to_account_info(),
+                self.pool_mint.to_account_info(),
+                self.system_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.stake_pool_program.to_account_info(),
+            ],
+            &[treasury_seeds],
+        )?;
+
+        self.pool_token_account.reload()?;
+        let pool = StakePoolState::load(&self.stake_pool)?;
+        let sol_strategy = &mut self.sol_strategy;
+        sol_strategy.sol_in_treasury -= lamports;
+        sol_strategy.stake_pool_lamports = pool.lamports_for(self.pool_token_account.amount);
+
+        emit!(StakePoolDeposited {
+            governance_authority: self.governance_authority.key(),
+            stake_pool: self.stake_pool.key(),
+            lamports,
+            pool_tokens: self.pool_token_account.amount - pool_tokens_before,
+            sol_in_treasury: sol_strategy.sol_in_treasury,
+            stake_pool_lamports: sol_strategy.stake_pool_lamports,
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The stake pool doesn't match the one configured in the SolStrategy.")]
+    InvalidStakePool,
+
+    #[msg("The pool mint doesn't match the mint of the stake pool.")]
+    InvalidPoolMint,
+
+    #[msg("The amount to deposit must be greater than zero.")]
+    InvalidDepositAmount,
+
+    #[msg("The treasury doesn't hold enough unstaked Sol to deposit into the stake pool.")]
+    InsufficientTreasuryBalance,
+}
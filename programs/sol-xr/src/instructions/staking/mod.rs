@@ -0,0 +1,8 @@
+pub mod create;
+pub mod delegate;
+pub mod deactivate;
+pub mod withdraw;
+pub mod sync;
+pub mod deposit_pool;
+pub mod withdraw_pool;
+pub mod sync_pool;
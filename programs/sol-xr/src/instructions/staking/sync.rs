@@ -0,0 +1,57 @@
+use {
+    crate::{
+        events::StakeSynced,
+        state::{native_stake::NativeStake, sol_strategy::SolStrategy},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct SyncStake<'info> {
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        seeds = [NativeStake::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub native_stake: Account<'info, NativeStake>,
+
+    /// CHECK: Stake account created by `create_stake`, only its lamports are read
+    #[account(
+        address = native_stake.stake_account @ Error::InvalidStakeAccount,
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+}
+
+impl<'info> SyncStake<'info> {
+    /// Counts the rewards paid into a stake account in `staked_lamports`, and so in NAV.
+    /// Anyone can call it.
+    pub fn handler(&mut self, _bumps: &SyncStakeBumps, id: u64) -> Result<()> {
+        let lamports = self.stake_account.lamports();
+
+        let sol_strategy = &mut self.sol_strategy;
+        sol_strategy.staked_lamports =
+            sol_strategy.staked_lamports - self.native_stake.lamports + lamports;
+        self.native_stake.lamports = lamports;
+
+        emit!(StakeSynced {
+            stake_id: id,
+            lamports,
+            staked_lamports: sol_strategy.staked_lamports,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The stake account doesn't match the one recorded for this stake id.")]
+    InvalidStakeAccount,
+}
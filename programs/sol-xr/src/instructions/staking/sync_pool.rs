@@ -0,0 +1,68 @@
+use {
+    crate::{
+        events::StakePoolSynced, state::sol_strategy::SolStrategy,
+        utils::stake_pool::StakePoolState,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct SyncStakePool<'info> {
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    /// CHECK: Stake pool configured in the SolStrategy, parsed by `StakePoolState`
+    #[account(
+        constraint = Some(stake_pool.key()) == sol_strategy.stake_pool @ Error::InvalidStakePool,
+    )]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    pub pool_mint: Account<'info, Mint>,
+
+    #[account(
+        associated_token::mint = pool_mint,
+        associated_token::authority = treasury,
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+}
+
+impl<'info> SyncStakePool<'info> {
+    /// Values the pool tokens held by the treasury at the current pool exchange rate, so
+    /// NAV includes the staking rewards. Anyone can call it.
+    pub fn handler(&mut self, _bumps: &SyncStakePoolBumps) -> Result<()> {
+        let pool = StakePoolState::load(&self.stake_pool)?;
+        require_keys_eq!(pool.pool_mint, self.pool_mint.key(), Error::InvalidPoolMint);
+
+        let sol_strategy = &mut self.sol_strategy;
+        sol_strategy.stake_pool_lamports = pool.lamports_for(self.pool_token_account.amount);
+
+        emit!(StakePoolSynced {
+            stake_pool: self.stake_pool.key(),
+            pool_tokens: self.pool_token_account.amount,
+            total_lamports: pool.total_lamports,
+            pool_token_supply: pool.pool_token_supply,
+            stake_pool_lamports: sol_strategy.stake_pool_lamports,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The stake pool doesn't match the one configured in the SolStrategy.")]
+    InvalidStakePool,
+
+    #[msg("The pool mint doesn't match the mint of the stake pool.")]
+    InvalidPoolMint,
+}
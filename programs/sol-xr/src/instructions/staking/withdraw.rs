@@ -0,0 +1,114 @@
+use {
+    crate::{
+        events::StakeWithdrawn,
+        state::{native_stake::NativeStake, sol_strategy::SolStrategy},
+        utils::invariants,
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            program::invoke_signed,
+            stake::{self, instruction as stake_instruction},
+            sysvar,
+        },
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        mut,
+        close = governance_authority,
+        seeds = [NativeStake::SEED_PREFIX, &id.to_le_bytes()],
+        bump
+    )]
+    pub native_stake: Account<'info, NativeStake>,
+
+    /// CHECK: Stake account created by `create_stake`, matched against the NativeStake
+    #[account(
+        mut,
+        address = native_stake.stake_account @ Error::InvalidStakeAccount,
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: Stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+    /// CHECK: Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+impl<'info> WithdrawStake<'info> {
+    /// Moves every lamport of an inactive stake account back to the treasury, rewards
+    /// that were not synced yet are credited along with the principal
+    pub fn handler(&mut self, bumps: &WithdrawStakeBumps, id: u64) -> Result<()> {
+        let lamports = self.stake_account.lamports();
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        invoke_signed(
+            &stake_instruction::withdraw(
+                self.stake_account.key,
+                self.treasury.key,
+                self.treasury.key,
+                lamports,
+                None,
+            ),
+            &[
+                self.stake_account.to_account_info(),
+                self.treasury.to_account_info(),
+                self.clock.to_account_info(),
+                self.stake_history.to_account_info(),
+                self.stake_program.to_account_info(),
+            ],
+            &[treasury_seeds],
+        )?;
+
+        let sol_strategy = &mut self.sol_strategy;
+        sol_strategy.staked_lamports -= self.native_stake.lamports;
+        sol_strategy.sol_in_treasury += lamports;
+
+        emit!(StakeWithdrawn {
+            governance_authority: self.governance_authority.key(),
+            stake_id: id,
+            lamports,
+            rewards: lamports.saturating_sub(self.native_stake.lamports),
+            sol_in_treasury: sol_strategy.sol_in_treasury,
+            staked_lamports: sol_strategy.staked_lamports,
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The stake account doesn't match the one recorded for this stake id.")]
+    InvalidStakeAccount,
+}
@@ -0,0 +1,164 @@
+use {
+    crate::{
+        events::StakePoolWithdrawn,
+        state::sol_strategy::SolStrategy,
+        utils::{
+            invariants,
+            stake_pool::{
+                self, StakePoolKeys, StakePoolState, STAKE_POOL_PROGRAM_ID,
+                WITHDRAW_AUTHORITY_SEED,
+            },
+        },
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{program::invoke_signed, stake, sysvar},
+    },
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawStakePool<'info> {
+    #[account(
+        constraint = sol_strategy.governance_authority.key() == governance_authority.key() @ Error::UnauthorizedGovernanceAuthority,
+    )]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    /// CHECK: Stake pool configured in the SolStrategy, parsed by `StakePoolState`
+    #[account(
+        mut,
+        constraint = Some(stake_pool.key()) == sol_strategy.stake_pool @ Error::InvalidStakePool,
+    )]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by PDA derivation
+    #[account(
+        seeds = [stake_pool.key().as_ref(), WITHDRAW_AUTHORITY_SEED],
+        bump,
+        seeds::program = stake_pool_program.key(),
+    )]
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = treasury,
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: Stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+    /// CHECK: Stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+    /// CHECK: SPL stake pool program
+    #[account(address = STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawStakePool<'info> {
+    /// Burns `pool_tokens` held by the treasury and withdraws their Sol from the pool reserve
+    /// back to the treasury
+    pub fn handler(
+        &mut self,
+        bumps: &WithdrawStakePoolBumps,
+        pool_tokens: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        require!(pool_tokens > 0, Error::InvalidWithdrawAmount);
+
+        let keys = StakePoolKeys {
+            stake_pool: self.stake_pool.key(),
+            withdraw_authority: self.stake_pool_withdraw_authority.key(),
+            reserve_stake: self.reserve_stake.key(),
+            manager_fee_account: self.manager_fee_account.key(),
+            pool_mint: self.pool_mint.key(),
+        };
+        let treasury_lamports_before = self.treasury.lamports();
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[bumps.treasury]];
+        invoke_signed(
+            &stake_pool::withdraw_sol(
+                &keys,
+                self.treasury.key,
+                &self.pool_token_account.key(),
+                self.treasury.key,
+                pool_tokens,
+                min_lamports_out,
+            ),
+            &[
+                self.stake_pool.to_account_info(),
+                self.stake_pool_withdraw_authority.to_account_info(),
+                self.treasury.to_account_info(),
+                self.pool_token_account.to_account_info(),
+                self.reserve_stake.to_account_info(),
+                self.manager_fee_account.to_account_info(),
+                self.pool_mint.to_account_info(),
+                self.clock.to_account_info(),
+                self.stake_history.to_account_info(),
+                self.stake_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.stake_pool_program.to_account_info(),
+            ],
+            &[treasury_seeds],
+        )?;
+        let lamports = self.treasury.lamports() - treasury_lamports_before;
+
+        self.pool_token_account.reload()?;
+        let pool = StakePoolState::load(&self.stake_pool)?;
+        let sol_strategy = &mut self.sol_strategy;
+        sol_strategy.sol_in_treasury += lamports;
+        sol_strategy.stake_pool_lamports = pool.lamports_for(self.pool_token_account.amount);
+
+        emit!(StakePoolWithdrawn {
+            governance_authority: self.governance_authority.key(),
+            stake_pool: self.stake_pool.key(),
+            pool_tokens,
+            lamports,
+            sol_in_treasury: sol_strategy.sol_in_treasury,
+            stake_pool_lamports: sol_strategy.stake_pool_lamports,
+        });
+
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
+    UnauthorizedGovernanceAuthority,
+
+    #[msg("The stake pool doesn't match the one configured in the SolStrategy.")]
+    InvalidStakePool,
+
+    #[msg("The amount of pool tokens to withdraw must be greater than zero.")]
+    InvalidWithdrawAmount,
+}
@@ -0,0 +1,65 @@
+use {
+    crate::{events::TreasurySynced, state::sol_strategy::SolStrategy},
+    anchor_lang::prelude::*,
+    anchor_spl::token::Mint,
+};
+
+#[derive(Accounts)]
+pub struct SyncTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [SolStrategy::SEED_PREFIX],
+        bump
+    )]
+    pub sol_strategy: Account<'info, SolStrategy>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"token"],
+        bump
+    )]
+    pub token: Account<'info, Mint>,
+}
+
+impl<'info> SyncTreasury<'info> {
+    /// Credits the treasury lamports not backed by `sol_in_treasury` or `sol_from_bond`, such as
+    /// donations or withdrawn rewards, to NAV. Anyone can call it.
+    pub fn handler(&mut self, _bumps: &SyncTreasuryBumps) -> Result<()> {
+        let treasury_lamports = self.treasury.lamports();
+        let available = treasury_lamports.saturating_sub(Rent::get()?.minimum_balance(0));
+
+        let sol_strategy = &mut self.sol_strategy;
+        let liabilities = sol_strategy.sol_in_treasury + sol_strategy.sol_from_bond;
+        if available < liabilities {
+            msg!(
+                "Treasury shortfall: {} lamports available for {} recorded",
+                available,
+                liabilities
+            );
+            return err!(Error::TreasuryShortfall);
+        }
+
+        let surplus = available - liabilities;
+        sol_strategy.sol_in_treasury += surplus;
+
+        emit!(TreasurySynced {
+            treasury_lamports,
+            surplus,
+            sol_in_treasury: sol_strategy.sol_in_treasury,
+            sol_from_bond: sol_strategy.sol_from_bond,
+            nav: sol_strategy.nav(self.token.supply),
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The treasury holds fewer lamports than the Sol recorded in the SolStrategy.")]
+    TreasuryShortfall,
+}
@@ -1,32 +1,30 @@
 use {
     crate::{
+        events::WhitelistPurchased,
         state::{
-            whitelists::{Whitelist, WhitelistRecord},
+            whitelists::{Whitelist, WhitelistNFTRecord, WhitelistRecord},
             sol_strategy::SolStrategy,
         },
+        utils::{
+            edition::{
+                create_pda_account, MasterEditionAccounts, PrintEditionAccounts,
+                PRINT_EDITION_ACCOUNTS,
+            },
+            invariants,
+            merkle::{check_allowlist, AllowlistProof},
+        },
     },
     anchor_lang::prelude::*,
     anchor_lang::system_program,
     anchor_spl::{
         associated_token::AssociatedToken,
-        metadata::{
-            mpl_token_metadata::{
-                accounts::MasterEdition,
-                instructions::{
-                    MintNewEditionFromMasterEditionViaTokenCpi,
-                    MintNewEditionFromMasterEditionViaTokenCpiAccounts,
-                    MintNewEditionFromMasterEditionViaTokenInstructionArgs,
-                },
-                types::{MintNewEditionFromMasterEditionViaTokenArgs},
-            },
-            Metadata,
-        },
-        token::{mint_to, Mint, MintTo, Token, TokenAccount},
+        metadata::{mpl_token_metadata::accounts::MasterEdition, Metadata},
+        token::{Mint, Token, TokenAccount},
     },
 };
 
 #[derive(Accounts)]
-#[instruction(id: u64)]
+#[instruction(id: u64, quantity: u64)]
 pub struct BuyWhitelist<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
@@ -61,6 +59,15 @@ pub struct BuyWhitelist<'info> {
     )]
     pub whitelist_record: Account<'info, WhitelistRecord>,
 
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + WhitelistNFTRecord::INIT_SPACE,
+        seeds = [WhitelistNFTRecord::SEED_PREFIX, whitelist.key().as_ref(), whitelist.next_edition_number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub whitelist_edition_record: Box<Account<'info, WhitelistNFTRecord>>,
+
     #[account(
         init,
         payer = buyer,
@@ -139,7 +146,21 @@ pub struct BuyWhitelist<'info> {
 }
 
 impl<'info> BuyWhitelist<'info> {
-    pub fn handler(&mut self, bumps: &BuyWhitelistBumps, _id: u64) -> Result<()> {
+    /// Mints `quantity` editions, the first through the named accounts and every additional one
+    /// through `PRINT_EDITION_ACCOUNTS` remaining accounts, followed by their edition records
+    pub fn handler(
+        &mut self,
+        bumps: &BuyWhitelistBumps,
+        remaining_accounts: &[AccountInfo<'info>],
+        id: u64,
+        quantity: u64,
+        allowlist: Option<AllowlistProof>,
+    ) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_WHITELISTS),
+            Error::ProtocolPaused
+        );
+
         let sol_strategy = &mut self.sol_strategy;
         let whitelist = &mut self.whitelist;
         let buyer = &mut self.buyer;
@@ -154,11 +175,35 @@ impl<'info> BuyWhitelist<'info> {
             Error::MintingEnded
         );
 
+        require!(quantity > 0, Error::InvalidQuantity);
         let whitelist_record = &mut self.whitelist_record;
         require!(
-            whitelist_record.minted < whitelist.max_mint_per_wallet,
+            whitelist_record.minted + quantity <= whitelist.max_mint_per_wallet,
             Error::MaxMintPerWalletReached
         );
+        if let Some(allocation) =
+            check_allowlist(whitelist.allowlist_root, &buyer.key(), allowlist.as_ref())?
+        {
+            require!(
+                whitelist_record.minted + quantity <= allocation,
+                Error::ExceedsAllocation
+            );
+        }
+        let additional_count = quantity as usize - 1;
+        require!(
+            remaining_accounts.len() == additional_count * (PRINT_EDITION_ACCOUNTS + 1),
+            Error::EditionRecordAccountsMismatch
+        );
+        let (print_edition_accounts, edition_record_accounts) =
+            remaining_accounts.split_at(additional_count * PRINT_EDITION_ACCOUNTS);
+        let additional_editions = PrintEditionAccounts::from_remaining_accounts(
+            print_edition_accounts,
+            additional_count,
+        )?;
+        let price = whitelist.current_price(clock.unix_timestamp as u64);
+        let total_price = price
+            .checked_mul(quantity)
+            .ok_or(Error::EditionOverflow)?;
 
         system_program::transfer(
             CpiContext::new(
@@ -168,7 +213,7 @@ impl<'info> BuyWhitelist<'info> {
                     to: self.treasury.to_account_info(),
                 },
             ),
-            whitelist.price,
+            total_price,
         )?;
 
         let current_supply: u64;
@@ -186,63 +231,116 @@ impl<'info> BuyWhitelist<'info> {
             next_edition_number = current_supply
                 .checked_add(1)
                 .ok_or(Error::EditionOverflow)?;
+
+            if let Some(max_supply) = master_edition_account.max_supply {
+                require!(current_supply + quantity <= max_supply, Error::MaxSupplyReached);
+            }
         }
         let sol_strategy_bump = bumps.sol_strategy;
         let sol_strategy_seeds: &[&[u8]] = &[SolStrategy::SEED_PREFIX, &[sol_strategy_bump]];
         let signer_seeds: &[&[&[u8]]] = &[&sol_strategy_seeds[..]];
-        let rent = &self.rent.to_account_info();
-
-        let mint_to_cpi_accounts = MintTo {
-            mint: self.buyer_whitelist_nft.to_account_info(),
-            to: self.buyer_token_account.to_account_info(),
-            authority: buyer.to_account_info(),
-        };
-        let mint_to_cpi_ctx =
-            CpiContext::new(self.token_program.to_account_info(), mint_to_cpi_accounts);
-        mint_to(mint_to_cpi_ctx, 1)?;
-
-        let cpi_accounts = MintNewEditionFromMasterEditionViaTokenCpiAccounts {
-            new_metadata: &self.buyer_metadata.to_account_info(),
-            new_edition: &self.buyer_edition.to_account_info(),
+        let master_edition_accounts = MasterEditionAccounts {
+            buyer: &buyer.to_account_info(),
+            owner: &sol_strategy.to_account_info(),
+            master_token_account: &self.whitelist_token_account.to_account_info(),
+            master_metadata: &self.whitelist_metadata.to_account_info(),
             master_edition: &self.whitelist_edition.to_account_info(),
-            new_mint: &self.buyer_whitelist_nft.to_account_info(),
-            edition_mark_pda: &self.edition_mark_pda.to_account_info(),
-            new_mint_authority: &buyer.to_account_info(),
-            payer: &buyer.to_account_info(),
-            token_account_owner: &sol_strategy.to_account_info(),
-            token_account: &self.whitelist_token_account.to_account_info(),
-            new_metadata_update_authority: &buyer.to_account_info(),
-            metadata: &self.whitelist_metadata.to_account_info(),
+            metadata_program: &self.metadata_program.to_account_info(),
             token_program: &self.token_program.to_account_info(),
+            associated_token_program: &self.associated_token_program.to_account_info(),
             system_program: &self.system_program.to_account_info(),
-            rent: Some(rent),
+            rent: &self.rent.to_account_info(),
         };
 
-        let instruction_args = MintNewEditionFromMasterEditionViaTokenInstructionArgs {
-            mint_new_edition_from_master_edition_via_token_args:
-                MintNewEditionFromMasterEditionViaTokenArgs {
-                    edition: next_edition_number,
-                },
-        };
+        master_edition_accounts.print(
+            &PrintEditionAccounts {
+                mint: &self.buyer_whitelist_nft.to_account_info(),
+                token_account: &self.buyer_token_account.to_account_info(),
+                metadata: &self.buyer_metadata.to_account_info(),
+                edition: &self.buyer_edition.to_account_info(),
+                edition_mark_pda: &self.edition_mark_pda.to_account_info(),
+            },
+            next_edition_number,
+            signer_seeds,
+        )?;
+        self.whitelist_edition_record.set_inner(WhitelistNFTRecord::new(price));
+        for (index, (print_edition, edition_record)) in additional_editions
+            .iter()
+            .zip(edition_record_accounts)
+            .enumerate()
+        {
+            let edition_number = next_edition_number + 1 + index as u64;
+            master_edition_accounts.create_and_print(
+                print_edition,
+                whitelist.key(),
+                edition_number,
+                signer_seeds,
+            )?;
+            Self::create_edition_record(
+                master_edition_accounts.buyer,
+                master_edition_accounts.system_program,
+                edition_record,
+                whitelist.key(),
+                edition_number,
+                WhitelistNFTRecord::new(price),
+            )?;
+        }
+        let last_edition_number = next_edition_number + quantity - 1;
 
-        MintNewEditionFromMasterEditionViaTokenCpi::new(
-            &self.metadata_program.to_account_info(),
-            cpi_accounts,
-            instruction_args,
-        )
-        .invoke_signed(signer_seeds)?;
+        whitelist_record.collection = whitelist.key();
+        whitelist_record.user = buyer.key();
+        whitelist_record.minted += quantity;
+        whitelist.next_edition_number = last_edition_number + 1;
+        whitelist.next_edition_marker = (last_edition_number + 1).checked_div(248).ok_or(Error::EditionOverflow)?.to_string();
+        sol_strategy.sol_in_treasury += total_price;
 
-        self.whitelist_record.set_inner(WhitelistRecord {
-            collection: whitelist.key(),
-            user: buyer.key(),
-            minted: 1,
+        emit!(WhitelistPurchased {
+            buyer: buyer.key(),
+            whitelist_id: id,
+            edition_number: next_edition_number,
+            quantity,
+            price,
+            sol_in_treasury: sol_strategy.sol_in_treasury,
         });
-        whitelist.next_edition_number = next_edition_number + 1;
-        whitelist.next_edition_marker = (next_edition_number + 1).checked_div(248).ok_or(Error::EditionOverflow)?.to_string();
-        sol_strategy.sol_in_treasury += whitelist.price;
 
+        invariants::check_treasury(&self.sol_strategy, self.treasury.lamports())?;
         Ok(())
     }
+
+    /// Creates the `WhitelistNFTRecord` PDA of an additional edition passed as a remaining account
+    fn create_edition_record(
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        edition_record: &AccountInfo<'info>,
+        whitelist: Pubkey,
+        edition_number: u64,
+        record: WhitelistNFTRecord,
+    ) -> Result<()> {
+        let edition_bytes = edition_number.to_le_bytes();
+        let (address, bump) = Pubkey::find_program_address(
+            &[WhitelistNFTRecord::SEED_PREFIX, whitelist.as_ref(), &edition_bytes],
+            &crate::ID,
+        );
+        require_keys_eq!(edition_record.key(), address, Error::InvalidEditionRecord);
+
+        let record_seeds: &[&[u8]] = &[
+            WhitelistNFTRecord::SEED_PREFIX,
+            whitelist.as_ref(),
+            &edition_bytes,
+            &[bump],
+        ];
+        create_pda_account(
+            payer,
+            system_program,
+            edition_record,
+            8 + WhitelistNFTRecord::INIT_SPACE,
+            &crate::ID,
+            &[record_seeds],
+        )?;
+
+        let mut data = edition_record.try_borrow_mut_data()?;
+        record.try_serialize(&mut &mut data[..])
+    }
 }
 
 #[error_code]
@@ -257,4 +355,16 @@ enum Error {
     AccountNotMasterEdition,
     #[msg("Cannot mint more editions, supply reached.")]
     EditionOverflow,
+    #[msg("Master edition max supply reached.")]
+    MaxSupplyReached,
+    #[msg("The quantity to mint must be greater than zero.")]
+    InvalidQuantity,
+    #[msg("Each additional edition needs its print accounts and its edition record.")]
+    EditionRecordAccountsMismatch,
+    #[msg("The edition record doesn't match the whitelist and edition number.")]
+    InvalidEditionRecord,
+    #[msg("The purchase exceeds the allocation of the buyer in the allowlist.")]
+    ExceedsAllocation,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
 }
@@ -1,12 +1,16 @@
 use {
-    crate::state::{
-        sol_strategy::SolStrategy,
-        whitelists::{Whitelist, WhitelistNFTRecord},
+    crate::{
+        events::WhitelistConverted,
+        state::{
+            sol_strategy::SolStrategy,
+            whitelists::{Whitelist, WhitelistNFTRecord},
+        },
+        utils::invariants,
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{mint_to, Mint, MintTo, Token, TokenAccount},
+        token::{burn, mint_to, transfer, Burn, Mint, MintTo, Token, TokenAccount, Transfer},
     },
 };
 
@@ -38,9 +42,7 @@ pub struct ConvertWhitelist<'info> {
     pub whitelist: Account<'info, Whitelist>,
 
     #[account(
-        init_if_needed,
-        payer = buyer,
-        space = 8 + WhitelistNFTRecord::INIT_SPACE,
+        mut,
         seeds = [WhitelistNFTRecord::SEED_PREFIX, whitelist.key().as_ref(), edition_number.to_le_bytes().as_ref()],
         bump
     )]
@@ -61,6 +63,15 @@ pub struct ConvertWhitelist<'info> {
     )]
     pub associated_nft_account: Account<'info, TokenAccount>,
 
+    /// Required when the whitelist locks converted editions instead of burning them
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = buyer_whitelist_nft,
+        associated_token::authority = sol_strategy,
+    )]
+    pub escrow_nft_account: Option<Account<'info, TokenAccount>>,
+
     #[account(
         mut,
         seeds = [b"token"],
@@ -85,9 +96,15 @@ impl<'info> ConvertWhitelist<'info> {
     pub fn handler(
         &mut self,
         bumps: &ConvertWhitelistBumps,
-        _id: u64,
-        _edition_number: u64,
+        id: u64,
+        edition_number: u64,
+        min_solxr_out: u64,
     ) -> Result<()> {
+        require!(
+            !self.sol_strategy.is_paused(SolStrategy::PAUSE_CONVERSIONS),
+            Error::ProtocolPaused
+        );
+
         let whitelist = &self.whitelist;
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp as u64;
@@ -105,6 +122,40 @@ impl<'info> ConvertWhitelist<'info> {
             Error::WhitelistExpired
         );
 
+        // Auction editions convert at the price they were bought for, not the starting price
+        let solxr_amount = self.whitelist_edition_record.paid_price;
+        require!(solxr_amount >= min_solxr_out, Error::SlippageExceeded);
+
+        let escrowed = whitelist.escrow_on_conversion;
+        if escrowed {
+            let Some(escrow_nft_account) = &self.escrow_nft_account else {
+                return err!(Error::EscrowAccountRequired);
+            };
+            transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.associated_nft_account.to_account_info(),
+                        to: escrow_nft_account.to_account_info(),
+                        authority: self.buyer.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
+        } else {
+            burn(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    Burn {
+                        mint: self.buyer_whitelist_nft.to_account_info(),
+                        from: self.associated_nft_account.to_account_info(),
+                        authority: self.buyer.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
+        }
+
         let sol_strategy_bump = bumps.sol_strategy;
         let sol_strategy_seeds: &[&[u8]] = &[SolStrategy::SEED_PREFIX, &[sol_strategy_bump]];
         let signer_seeds: &[&[&[u8]]] = &[&sol_strategy_seeds[..]];
@@ -119,10 +170,23 @@ impl<'info> ConvertWhitelist<'info> {
                 },
             )
             .with_signer(signer_seeds),
-            whitelist.price,
+            solxr_amount,
         )?;
 
         self.whitelist_edition_record.converted = true;
+        self.sol_strategy.solxr_issued += solxr_amount;
+
+        self.token.reload()?;
+        emit!(WhitelistConverted {
+            holder: self.buyer.key(),
+            whitelist_id: id,
+            edition_number,
+            escrowed,
+            solxr_minted: solxr_amount,
+            nav: self.sol_strategy.nav(self.token.supply),
+        });
+
+        invariants::check_solxr_supply(&self.sol_strategy, self.token.supply)?;
         Ok(())
     }
 }
@@ -137,4 +201,10 @@ enum Error {
     WhitelistExpired,
     #[msg("The token account must contain exactly 1 NFT.")]
     InvalidTokenAmount,
+    #[msg("The escrow token account is required to lock the converted edition.")]
+    EscrowAccountRequired,
+    #[msg("The Solxr minted is below the minimum accepted by the holder.")]
+    SlippageExceeded,
+    #[msg("This instruction is paused by governance.")]
+    ProtocolPaused,
 }
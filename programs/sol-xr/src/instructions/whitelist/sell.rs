@@ -1,5 +1,8 @@
 use {
-    crate::state::{whitelists::Whitelist, sol_strategy::SolStrategy},
+    crate::{
+        events::WhitelistIssued,
+        state::{auction::DutchAuction, whitelists::Whitelist, sol_strategy::SolStrategy},
+    },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
@@ -94,17 +97,30 @@ impl<'info> SellWhitelist<'info> {
         price: u64,
         maturity: u64,
         expiration: u64,
+        supply: u64,
         max_mint_per_wallet: u64,
         start_time: u64,
         end_time: u64,
+        escrow_on_conversion: bool,
+        auction: Option<DutchAuction>,
+        allowlist_root: Option<[u8; 32]>,
     ) -> Result<()> {
+        require!(supply > 0, Error::InvalidSupply);
+        if let Some(auction) = &auction {
+            require!(auction.is_valid(start_time, end_time), Error::InvalidAuction);
+        }
+
         self.whitelist.set_inner(Whitelist {
             maturity,
             expiration,
+            supply,
             price,
             max_mint_per_wallet,
             start_time,
             end_time,
+            auction,
+            allowlist_root,
+            escrow_on_conversion,
             next_edition_number: 1u64,
             next_edition_marker: (1 / 248).to_string(),
         });
@@ -178,11 +194,25 @@ impl<'info> SellWhitelist<'info> {
                 rent: Some(rent),
             },
             CreateMasterEditionV3InstructionArgs {
-                max_supply: None,
+                max_supply: Some(supply),
             },
         );
         master_edition_account.invoke_signed(mint_auth_signer)?;
 
+        emit!(WhitelistIssued {
+            governance_authority: self.governance_authority.key(),
+            whitelist_id: self.sol_strategy.next_whitelist_id,
+            price,
+            maturity,
+            expiration,
+            supply,
+            start_time,
+            end_time,
+            escrow_on_conversion,
+            auction,
+            allowlist_root,
+        });
+
         self.sol_strategy.next_whitelist_id += 1;
 
         Ok(())
@@ -193,4 +223,8 @@ impl<'info> SellWhitelist<'info> {
 enum Error {
     #[msg("Caller is not the required governance authority defined in the SolStrategy.")]
     UnauthorizedGovernanceAuthority,
+    #[msg("The whitelist supply must be greater than zero.")]
+    InvalidSupply,
+    #[msg("The auction needs a floor price below the start price and a decay that fits the sale period.")]
+    InvalidAuction,
 }
@@ -1,10 +1,13 @@
 pub mod constants;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -26,18 +29,37 @@ pub mod sol_xr {
     pub fn invest(ctx: Context<Invest>, amount: u64) -> Result<()> {
         ctx.accounts.handler(&ctx.bumps, amount)
     }
+    pub fn redeem_solxr(ctx: Context<RedeemSolxr>, amount: u64, min_sol_out: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, amount, min_sol_out)
+    }
     pub fn open_mint_round(
         ctx: Context<OpenMintingRound>,
         id: u64,
         market_value: u64,
+        curve: mint_round::PriceCurve,
+        allowlist_root: Option<[u8; 32]>,
     ) -> Result<()> {
-        ctx.accounts.handler(&ctx.bumps, id, market_value)
+        ctx.accounts
+            .handler(&ctx.bumps, id, market_value, curve, allowlist_root)
     }
     pub fn close_mint_round(ctx: Context<CloseMintingRound>) -> Result<()> {
         ctx.accounts.handler(&ctx.bumps)
     }
-    pub fn buy_solxr(ctx: Context<BuySolxr>, id: u64, amount: u64) -> Result<()> {
-        ctx.accounts.handler(&ctx.bumps, id, amount)
+    pub fn buy_solxr<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuySolxr<'info>>,
+        id: u64,
+        amount: u64,
+        min_solxr_out: u64,
+        allowlist: Option<utils::merkle::AllowlistProof>,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            &ctx.bumps,
+            ctx.remaining_accounts,
+            id,
+            amount,
+            min_solxr_out,
+            allowlist,
+        )
     }
 
     pub fn sell_bond(
@@ -52,6 +74,13 @@ pub mod sol_xr {
         max_mint_per_wallet: u64,
         start_time: u64,
         end_time: u64,
+        coupon_rate: u64,
+        coupon_interval: u64,
+        early_redemption_penalty: u64,
+        redemption_deadline: u64,
+        conversion_premium: Option<u64>,
+        auction: Option<auction::DutchAuction>,
+        allowlist_root: Option<[u8; 32]>,
     ) -> Result<()> {
         ctx.accounts.handler(
             &ctx.bumps,
@@ -65,19 +94,52 @@ pub mod sol_xr {
             max_mint_per_wallet,
             start_time,
             end_time,
+            coupon_rate,
+            coupon_interval,
+            early_redemption_penalty,
+            redemption_deadline,
+            conversion_premium,
+            auction,
+            allowlist_root,
         )
     }
-    pub fn buy_bond(ctx: Context<BuyBond>, id: u64) -> Result<()> {
-        ctx.accounts.handler(&ctx.bumps, id)
+    pub fn buy_bond<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyBond<'info>>,
+        id: u64,
+        quantity: u64,
+        allowlist: Option<utils::merkle::AllowlistProof>,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(&ctx.bumps, ctx.remaining_accounts, id, quantity, allowlist)
+    }
+    pub fn claim_coupon(ctx: Context<ClaimCoupon>, id: u64, edition_number: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, id, edition_number)
+    }
+    pub fn redeem_bond_early(
+        ctx: Context<RedeemBondEarly>,
+        id: u64,
+        edition_number: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(&ctx.bumps, id, edition_number, min_sol_out)
+    }
+    pub fn get_bond_edition_status(
+        ctx: Context<GetBondEditionStatus>,
+        id: u64,
+        edition_number: u64,
+    ) -> Result<bonds::BondEditionRecord> {
+        ctx.accounts.handler(&ctx.bumps, id, edition_number)
     }
     pub fn convert_bond(
         ctx: Context<ConvertBond>,
         id: u64,
         edition_number: u64,
         convert: bool,
+        min_out: u64,
     ) -> Result<()> {
         ctx.accounts
-            .handler(&ctx.bumps, id, edition_number, convert)
+            .handler(&ctx.bumps, id, edition_number, convert, min_out)
     }
     pub fn sell_whitelist(
         ctx: Context<SellWhitelist>,
@@ -87,9 +149,13 @@ pub mod sol_xr {
         price: u64,
         maturity: u64,
         expiration: u64,
+        supply: u64,
         max_mint_per_wallet: u64,
         start_time: u64,
         end_time: u64,
+        escrow_on_conversion: bool,
+        auction: Option<auction::DutchAuction>,
+        allowlist_root: Option<[u8; 32]>,
     ) -> Result<()> {
         ctx.accounts.handler(
             &ctx.bumps,
@@ -99,19 +165,119 @@ pub mod sol_xr {
             price,
             maturity,
             expiration,
+            supply,
             max_mint_per_wallet,
             start_time,
             end_time,
+            escrow_on_conversion,
+            auction,
+            allowlist_root,
         )
     }
-    pub fn buy_whitelist(ctx: Context<BuyWhitelist>, id: u64) -> Result<()> {
-        ctx.accounts.handler(&ctx.bumps, id)
+    pub fn buy_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyWhitelist<'info>>,
+        id: u64,
+        quantity: u64,
+        allowlist: Option<utils::merkle::AllowlistProof>,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(&ctx.bumps, ctx.remaining_accounts, id, quantity, allowlist)
     }
     pub fn convert_whitelist(
         ctx: Context<ConvertWhitelist>,
         id: u64,
         edition_number: u64,
+        min_solxr_out: u64,
     ) -> Result<()> {
-        ctx.accounts.handler(&ctx.bumps, id, edition_number)
+        ctx.accounts
+            .handler(&ctx.bumps, id, edition_number, min_solxr_out)
+    }
+    pub fn create_stake(ctx: Context<CreateStake>, amount: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, amount)
+    }
+    pub fn delegate_stake(ctx: Context<DelegateStake>, id: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, id)
+    }
+    pub fn deactivate_stake(ctx: Context<DeactivateStake>, id: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, id)
+    }
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, id: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, id)
+    }
+    pub fn sync_stake(ctx: Context<SyncStake>, id: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, id)
+    }
+
+    pub fn deposit_stake_pool(
+        ctx: Context<DepositStakePool>,
+        lamports: u64,
+        min_pool_tokens_out: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(&ctx.bumps, lamports, min_pool_tokens_out)
+    }
+    pub fn withdraw_stake_pool(
+        ctx: Context<WithdrawStakePool>,
+        pool_tokens: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(&ctx.bumps, pool_tokens, min_lamports_out)
+    }
+    pub fn sync_stake_pool(ctx: Context<SyncStakePool>) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps)
+    }
+
+    pub fn queue_governance_action(
+        ctx: Context<QueueGovernanceAction>,
+        action: governance_action::TimelockedAction,
+        eta: u64,
+    ) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, action, eta)
+    }
+    pub fn execute_governance_action(ctx: Context<ExecuteGovernanceAction>, id: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, id)
+    }
+    pub fn cancel_governance_action(ctx: Context<CancelGovernanceAction>, id: u64) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, id)
+    }
+    pub fn accept_governance_authority(ctx: Context<AcceptGovernanceAuthority>) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps)
+    }
+    pub fn cancel_governance_authority(ctx: Context<CancelGovernanceAuthority>) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps)
+    }
+    pub fn set_pause(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, paused)
+    }
+    pub fn pause(ctx: Context<Pause>, flags: u8) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, flags)
+    }
+    pub fn sync_treasury(ctx: Context<SyncTreasury>) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps)
+    }
+    pub fn check_invariants(ctx: Context<CheckInvariants>, pause_on_violation: bool) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, pause_on_violation)
+    }
+    pub fn check_sol_from_bond<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckSolFromBond<'info>>,
+        pause_on_violation: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(&ctx.bumps, ctx.remaining_accounts, pause_on_violation)
+    }
+    pub fn check_bond_reserves<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckBondReserves<'info>>,
+        id: u64,
+        pause_on_violation: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(&ctx.bumps, ctx.remaining_accounts, id, pause_on_violation)
+    }
+    pub fn record_price_observation(
+        ctx: Context<RecordPriceObservation>,
+        price: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.handler(&ctx.bumps, price)
     }
 }
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+/// Descending price charged between the start and end time of a bond or whitelist sale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct DutchAuction {
+    pub start_price: u64,
+    /// Price never goes below this value, it is charged once the sale reached its end time
+    pub floor_price: u64,
+    pub decay: PriceDecay,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum PriceDecay {
+    /// Price goes down linearly from the start price to the floor price over the sale
+    Linear,
+    /// Price is cut by `step_rate` every `step_interval` seconds, scaled so 1_000_000_000 = 100%
+    ExponentialStep { step_interval: u64, step_rate: u64 },
+}
+
+impl DutchAuction {
+    pub fn is_valid(&self, start_time: u64, end_time: u64) -> bool {
+        if self.floor_price == 0 || self.start_price < self.floor_price {
+            return false;
+        }
+        match self.decay {
+            PriceDecay::Linear => end_time > start_time,
+            PriceDecay::ExponentialStep {
+                step_interval,
+                step_rate,
+            } => step_interval > 0 && step_rate > 0 && step_rate <= u64::pow(10, 9),
+        }
+    }
+
+    /// Price charged at `timestamp` for a sale running from `start_time` to `end_time`
+    pub fn price_at(&self, start_time: u64, end_time: u64, timestamp: u64) -> u64 {
+        let elapsed = timestamp.saturating_sub(start_time);
+        let price = match self.decay {
+            PriceDecay::Linear => {
+                let duration = end_time.saturating_sub(start_time);
+                if duration == 0 || elapsed >= duration {
+                    return self.floor_price;
+                }
+                let decrease = (self.start_price - self.floor_price) as u128 * elapsed as u128
+                    / duration as u128;
+                self.start_price as u128 - decrease
+            }
+            PriceDecay::ExponentialStep {
+                step_interval,
+                step_rate,
+            } => {
+                let steps = elapsed / step_interval;
+                let factor = Self::pow_scaled(u128::pow(10, 9) - step_rate as u128, steps);
+                self.start_price as u128 * factor / u128::pow(10, 9)
+            }
+        };
+        (price as u64).max(self.floor_price)
+    }
+
+    /// `base ^ exponent` for a base scaled so 1_000_000_000 = 1
+    fn pow_scaled(mut base: u128, mut exponent: u64) -> u128 {
+        let mut result = u128::pow(10, 9);
+        while exponent > 0 && result > 0 {
+            if exponent & 1 == 1 {
+                result = result * base / u128::pow(10, 9);
+            }
+            base = base * base / u128::pow(10, 9);
+            exponent >>= 1;
+        }
+        result
+    }
+}
@@ -1,16 +1,42 @@
 use anchor_lang::prelude::*;
 use anchor_lang::{account, InitSpace};
 
+use crate::state::auction::DutchAuction;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Bond {
     pub maturity: u64,
     pub strike_price: u64,
+    /// Premium over NAV used to derive `strike_price` at issuance, None when it was set by governance
+    pub conversion_premium: Option<u64>,
+    /// NAV when the bond was issued, zero when the strike price was set by governance
+    pub issuance_nav: u64,
     pub supply: u64,
+    /// Face value the coupons are computed on, also the price charged when there is no auction
     pub price: u64,
     pub max_mint_per_wallet: u64,
     pub start_time: u64,
     pub end_time: u64,
+    /// Descending price charged instead of `price` between `start_time` and `end_time`
+    pub auction: Option<DutchAuction>,
+    /// Merkle root of the `(address, allocation)` allowlist, None when anyone can buy
+    pub allowlist_root: Option<[u8; 32]>,
+    /// Coupon paid every `coupon_interval` as a share of the price paid for the edition, scaled so
+    /// 1_000_000_000 = 100%
+    pub coupon_rate: u64,
+    /// Seconds between two coupons counted from `start_time`, zero for a zero-coupon bond
+    pub coupon_interval: u64,
+    /// Share of the price kept when redeeming at `start_time`, decays linearly to zero at maturity
+    pub early_redemption_penalty: u64,
+    /// Conversion closes after this timestamp, unconverted editions can still be redeemed for Sol
+    /// without any time limit
+    pub redemption_deadline: u64,
+    /// Sol held for the outstanding editions of this bond, principal and coupons
+    pub sol_reserved: u64,
+    /// Bumped whenever `sol_reserved` changes, so a reserve audit spanning several
+    /// transactions knows it has to start over
+    pub reserve_updates: u64,
     pub next_edition_number: u64,
     #[max_len(248)]
     pub next_edition_marker: String,
@@ -18,6 +44,50 @@ pub struct Bond {
 
 impl Bond {
     pub const SEED_PREFIX: &'static [u8] = b"bond";
+
+    /// Price of one edition bought at `timestamp`
+    pub fn current_price(&self, timestamp: u64) -> u64 {
+        match &self.auction {
+            Some(auction) => auction.price_at(self.start_time, self.end_time, timestamp),
+            None => self.price,
+        }
+    }
+
+    /// Sol paid for one coupon period to an edition bought at `paid_price`
+    pub fn coupon_amount(&self, paid_price: u64) -> u64 {
+        let coupon = paid_price as u128 * self.coupon_rate as u128 / u128::pow(10, 9);
+        coupon as u64
+    }
+
+    /// Number of coupon periods elapsed at `timestamp`, coupons stop accruing at maturity
+    pub fn coupon_periods_at(&self, timestamp: u64) -> u64 {
+        if self.coupon_interval == 0 {
+            return 0;
+        }
+        timestamp.min(self.maturity).saturating_sub(self.start_time) / self.coupon_interval
+    }
+
+    /// Coupons owed to one edition bought at `paid_price` and `timestamp`, the periods elapsed
+    /// before are not paid
+    pub fn coupon_liability_at(&self, paid_price: u64, timestamp: u64) -> u64 {
+        self.coupon_amount(paid_price)
+            * (self.coupon_periods_at(self.maturity) - self.coupon_periods_at(timestamp))
+    }
+
+    /// Sol kept by the treasury when one edition paid `paid_price` is redeemed at `timestamp`
+    /// before maturity
+    pub fn early_redemption_penalty_at(&self, paid_price: u64, timestamp: u64) -> u64 {
+        let duration = self.maturity.saturating_sub(self.start_time);
+        if duration == 0 {
+            return 0;
+        }
+        let remaining = self.maturity.saturating_sub(timestamp).min(duration);
+        let penalty =
+            paid_price as u128 * self.early_redemption_penalty as u128 * remaining as u128
+                / u128::pow(10, 9)
+                / duration as u128;
+        penalty as u64
+    }
 }
 
 #[account]
@@ -29,4 +99,102 @@ pub struct BondRecord {
 }
 impl BondRecord {
     pub const SEED_PREFIX: &'static [u8] = b"bond_record";
-}
\ No newline at end of file
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BondEditionRecord {
+    pub bond: Pubkey,
+    pub edition_number: u64,
+    /// Sol paid for the edition, returned when it is redeemed
+    pub paid_price: u64,
+    /// Number of coupon periods already paid to the edition holders
+    pub claimed_coupon_periods: u64,
+    pub converted: bool,
+    /// Holder that burned the edition
+    pub converter: Pubkey,
+    pub converted_at: u64,
+    pub outcome: BondOutcome,
+}
+impl BondEditionRecord {
+    pub const SEED_PREFIX: &'static [u8] = b"bond_edition_record";
+
+    /// `claimed_coupon_periods` starts at the periods elapsed at purchase, the buyer didn't hold
+    /// the edition during those
+    pub fn new(
+        bond: Pubkey,
+        edition_number: u64,
+        paid_price: u64,
+        claimed_coupon_periods: u64,
+    ) -> Self {
+        Self {
+            bond,
+            edition_number,
+            paid_price,
+            claimed_coupon_periods,
+            converted: false,
+            converter: Pubkey::default(),
+            converted_at: 0,
+            outcome: BondOutcome::Outstanding,
+        }
+    }
+
+    /// Sol the bond still holds for this edition, its principal and unclaimed coupons
+    pub fn reserve(&self, bond: &Bond) -> u64 {
+        if self.converted {
+            return 0;
+        }
+        let unclaimed_periods = bond
+            .coupon_periods_at(bond.maturity)
+            .saturating_sub(self.claimed_coupon_periods);
+        self.paid_price + bond.coupon_amount(self.paid_price) * unclaimed_periods
+    }
+
+    pub fn record_outcome(&mut self, outcome: BondOutcome, converter: Pubkey, timestamp: u64) {
+        self.converted = true;
+        self.converter = converter;
+        self.converted_at = timestamp;
+        self.outcome = outcome;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum BondOutcome {
+    /// The edition has not been converted nor redeemed yet
+    Outstanding,
+    /// Converted into Solxr at maturity
+    Solxr,
+    /// Redeemed for Sol at maturity
+    Sol,
+    /// Redeemed for Sol before maturity with a penalty
+    EarlySol,
+}
+/// Progress of a reserve audit walking the edition records of a bond over several transactions
+#[account]
+#[derive(InitSpace)]
+pub struct BondReserveAudit {
+    /// `Bond::reserve_updates` when the audit started
+    pub reserve_updates: u64,
+    /// Next edition record to count, zero when no audit is in progress
+    pub next_edition_number: u64,
+    /// Reserve of the edition records counted so far
+    pub outstanding: u64,
+}
+impl BondReserveAudit {
+    pub const SEED_PREFIX: &'static [u8] = b"bond_reserve_audit";
+}
+
+/// Progress of an audit summing the Sol reserved by every bond over several transactions
+#[account]
+#[derive(InitSpace)]
+pub struct SolFromBondAudit {
+    /// `SolStrategy::sol_from_bond_updates` when the audit started
+    pub sol_from_bond_updates: u64,
+    /// Next bond to count, zero when no audit is in progress
+    pub next_bond_id: u64,
+    /// Sol reserved by the bonds counted so far
+    pub sol_reserved: u64,
+}
+impl SolFromBondAudit {
+    pub const SEED_PREFIX: &'static [u8] = b"sol_from_bond_audit";
+}
@@ -0,0 +1,525 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{account, InitSpace};
+
+use crate::{
+    events::{
+        GovernanceAuthorityProposed, GuardianUpdated, OracleConfigured, PlatformAddressUpdated,
+        PlatformFeeSplitsUpdated, RedemptionParamsUpdated, StakePoolUpdated, StrategyParam,
+        StrategyParamUpdated, TwapConfigured,
+    },
+    state::{
+        price_observation::{PriceObservation, MAX_PRICE_OBSERVATIONS},
+        sol_strategy::{FeeSplit, SolStrategy},
+    },
+    BASIS_POINTS, MAX_GOVERNANCE_DELAY, MAX_MINT_DURATION, MAX_PLATFORM_FEE_SPLITS,
+    MAX_PLATFORM_MINT_FEE_CEILING, MIN_GOVERNANCE_DELAY, MIN_PREMIUM_NAV_RATIO_CEILING,
+    NAV_GROWTH_RATE_CEILING, ORACLE_MAX_CONFIDENCE_CEILING, ORACLE_MAX_DEVIATION_CEILING,
+    ORACLE_MAX_STALENESS_CEILING, PLATFORM_MINT_FEE_CEILING, REDEMPTION_FEE_CEILING,
+};
+
+/// Governance action waiting in the timelock queue
+#[account]
+#[derive(InitSpace)]
+pub struct GovernanceAction {
+    pub id: u64,
+    /// Governance authority that queued the action, refunded the rent once executed or cancelled
+    pub proposer: Pubkey,
+    pub action: TimelockedAction,
+    /// Timestamp from which anyone can execute the action
+    pub eta: u64,
+}
+
+impl GovernanceAction {
+    pub const SEED_PREFIX: &'static [u8] = b"governance_action";
+}
+
+/// Sensitive governance changes that only take effect once the governance delay elapsed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum TimelockedAction {
+    /// None leaves a param unchanged
+    UpdateStrategyParams {
+        platform_mint_fee: Option<u64>,
+        max_platform_mint_fee: Option<u64>,
+        max_mint_per_wallet: Option<u64>,
+        min_premium_nav_ratio: Option<u64>,
+        nav_growth_rate: Option<u64>,
+        minting_rounds: Option<u64>,
+        mint_duration: Option<u64>,
+    },
+    /// The proposed authority still has to accept it
+    ProposeGovernanceAuthority {
+        new_governance_authority: Pubkey,
+    },
+    UpdatePlatformAddress {
+        platform_address: Pubkey,
+    },
+    UpdatePlatformFeeSplits {
+        #[max_len(MAX_PLATFORM_FEE_SPLITS)]
+        platform_fee_splits: Vec<FeeSplit>,
+    },
+    /// None leaves a param unchanged
+    UpdateRedemptionParams {
+        allow_redemption: Option<bool>,
+        redemption_fee: Option<u64>,
+        redemption_cap_per_epoch: Option<u64>,
+    },
+    /// Stake pool the treasury Sol is deposited into
+    SetStakePool {
+        stake_pool: Option<Pubkey>,
+    },
+    SetGovernanceDelay {
+        governance_delay: u64,
+    },
+    /// None removes the guardian, only governance can pause then
+    SetGuardian {
+        guardian: Option<Pubkey>,
+    },
+    /// None disables the oracle check on the market value of mint rounds
+    ConfigureOracle {
+        price_oracle: Option<Pubkey>,
+        max_staleness: u64,
+        max_confidence: u64,
+        max_deviation: u64,
+    },
+    /// A zero window disables the TWAP check on the market value of mint rounds
+    ConfigureTwap {
+        twap_window: u64,
+        min_observation_interval: u64,
+        price_reporter: Option<Pubkey>,
+        pool_solxr_vault: Option<Pubkey>,
+        pool_sol_vault: Option<Pubkey>,
+    },
+}
+
+impl TimelockedAction {
+    /// Checked when the action is queued and again when it's executed
+    pub fn validate(&self, sol_strategy: &SolStrategy) -> Result<()> {
+        match self {
+            Self::UpdateStrategyParams {
+                platform_mint_fee,
+                max_platform_mint_fee,
+                max_mint_per_wallet,
+                min_premium_nav_ratio,
+                nav_growth_rate,
+                minting_rounds,
+                mint_duration,
+            } => {
+                if let Some(platform_mint_fee) = *platform_mint_fee {
+                    require!(
+                        platform_mint_fee <= PLATFORM_MINT_FEE_CEILING,
+                        Error::PlatformMintFeeTooHigh
+                    );
+                }
+                if let Some(max_platform_mint_fee) = *max_platform_mint_fee {
+                    require!(
+                        max_platform_mint_fee <= MAX_PLATFORM_MINT_FEE_CEILING,
+                        Error::MaxPlatformMintFeeTooHigh
+                    );
+                }
+                if let Some(max_mint_per_wallet) = *max_mint_per_wallet {
+                    require!(max_mint_per_wallet > 0, Error::InvalidMaxMintPerWallet);
+                }
+                if let Some(min_premium_nav_ratio) = *min_premium_nav_ratio {
+                    require!(
+                        min_premium_nav_ratio <= MIN_PREMIUM_NAV_RATIO_CEILING,
+                        Error::MinPremiumNavRatioTooHigh
+                    );
+                }
+                if let Some(nav_growth_rate) = *nav_growth_rate {
+                    require!(
+                        nav_growth_rate > 0 && nav_growth_rate <= NAV_GROWTH_RATE_CEILING,
+                        Error::InvalidNavGrowthRate
+                    );
+                }
+                if let Some(minting_rounds) = *minting_rounds {
+                    require!(
+                        minting_rounds >= sol_strategy.next_minting_rounds,
+                        Error::MintingRoundsBelowNextRound
+                    );
+                }
+                if let Some(mint_duration) = *mint_duration {
+                    require!(
+                        mint_duration > 0 && mint_duration <= MAX_MINT_DURATION,
+                        Error::InvalidMintDuration
+                    );
+                }
+            }
+            Self::ProposeGovernanceAuthority {
+                new_governance_authority,
+            } => {
+                require!(
+                    *new_governance_authority != Pubkey::default(),
+                    Error::InvalidGovernanceAuthority
+                );
+                require!(
+                    *new_governance_authority != sol_strategy.governance_authority,
+                    Error::InvalidGovernanceAuthority
+                );
+            }
+            Self::UpdatePlatformAddress { platform_address } => {
+                require!(
+                    *platform_address != Pubkey::default(),
+                    Error::InvalidPlatformAddress
+                );
+            }
+            Self::UpdatePlatformFeeSplits {
+                platform_fee_splits,
+            } => {
+                require!(
+                    platform_fee_splits.len() <= MAX_PLATFORM_FEE_SPLITS,
+                    Error::TooManyFeeSplits
+                );
+
+                let mut total_basis_points: u64 = 0;
+                for (index, split) in platform_fee_splits.iter().enumerate() {
+                    require!(
+                        split.recipient != Pubkey::default(),
+                        Error::InvalidFeeRecipient
+                    );
+                    require!(
+                        platform_fee_splits[..index]
+                            .iter()
+                            .all(|other| other.recipient != split.recipient),
+                        Error::DuplicateFeeRecipient
+                    );
+                    require!(split.basis_points > 0, Error::InvalidFeeSplit);
+                    total_basis_points += split.basis_points as u64;
+                }
+                require!(
+                    total_basis_points <= BASIS_POINTS,
+                    Error::FeeSplitsExceedTotal
+                );
+            }
+            Self::UpdateRedemptionParams { redemption_fee, .. } => {
+                if let Some(redemption_fee) = *redemption_fee {
+                    require!(
+                        redemption_fee <= REDEMPTION_FEE_CEILING,
+                        Error::RedemptionFeeTooHigh
+                    );
+                }
+            }
+            // The treasury pool token balance is checked on execution
+            Self::SetStakePool { .. } => {}
+            Self::SetGovernanceDelay { governance_delay } => {
+                require!(
+                    (MIN_GOVERNANCE_DELAY..=MAX_GOVERNANCE_DELAY).contains(governance_delay),
+                    Error::InvalidGovernanceDelay
+                );
+            }
+            Self::SetGuardian { guardian } => {
+                require!(*guardian != Some(Pubkey::default()), Error::InvalidGuardian);
+            }
+            Self::ConfigureOracle {
+                price_oracle,
+                max_staleness,
+                max_confidence,
+                max_deviation,
+            } => {
+                if price_oracle.is_some() {
+                    require!(
+                        *max_staleness > 0 && *max_staleness <= ORACLE_MAX_STALENESS_CEILING,
+                        Error::InvalidMaxStaleness
+                    );
+                    require!(
+                        *max_confidence <= ORACLE_MAX_CONFIDENCE_CEILING,
+                        Error::MaxConfidenceTooHigh
+                    );
+                    require!(
+                        *max_deviation <= ORACLE_MAX_DEVIATION_CEILING,
+                        Error::MaxDeviationTooHigh
+                    );
+                }
+            }
+            Self::ConfigureTwap {
+                twap_window,
+                min_observation_interval,
+                price_reporter,
+                pool_solxr_vault,
+                pool_sol_vault,
+            } => {
+                require!(
+                    pool_solxr_vault.is_some() == pool_sol_vault.is_some(),
+                    Error::IncompletePoolVaults
+                );
+                if *twap_window > 0 {
+                    require!(
+                        price_reporter.is_some() || pool_solxr_vault.is_some(),
+                        Error::MissingPriceSource
+                    );
+                    // The ring buffer must be able to hold enough observations to span the window
+                    require!(
+                        *min_observation_interval > 0
+                            && *twap_window
+                                <= min_observation_interval * (MAX_PRICE_OBSERVATIONS as u64 - 1),
+                        Error::WindowExceedsObservationCapacity
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies a validated action to the SolStrategy, and to the price observation for
+    /// `ConfigureTwap`
+    pub fn apply(
+        &self,
+        sol_strategy: &mut SolStrategy,
+        price_observation: Option<&mut PriceObservation>,
+    ) -> Result<()> {
+        match self {
+            Self::UpdateStrategyParams {
+                platform_mint_fee,
+                max_platform_mint_fee,
+                max_mint_per_wallet,
+                min_premium_nav_ratio,
+                nav_growth_rate,
+                minting_rounds,
+                mint_duration,
+            } => {
+                let updates = [
+                    (
+                        StrategyParam::PlatformMintFee,
+                        &mut sol_strategy.platform_mint_fee,
+                        *platform_mint_fee,
+                    ),
+                    (
+                        StrategyParam::MaxPlatformMintFee,
+                        &mut sol_strategy.max_platform_mint_fee,
+                        *max_platform_mint_fee,
+                    ),
+                    (
+                        StrategyParam::MaxMintPerWallet,
+                        &mut sol_strategy.max_mint_per_wallet,
+                        *max_mint_per_wallet,
+                    ),
+                    (
+                        StrategyParam::MinPremiumNavRatio,
+                        &mut sol_strategy.min_premium_nav_ratio,
+                        *min_premium_nav_ratio,
+                    ),
+                    (
+                        StrategyParam::NavGrowthRate,
+                        &mut sol_strategy.nav_growth_rate,
+                        *nav_growth_rate,
+                    ),
+                    (
+                        StrategyParam::MintingRounds,
+                        &mut sol_strategy.minting_rounds,
+                        *minting_rounds,
+                    ),
+                    (
+                        StrategyParam::MintDuration,
+                        &mut sol_strategy.mint_duration,
+                        *mint_duration,
+                    ),
+                ];
+                for (param, current_value, new_value) in updates {
+                    if let Some(new_value) = new_value {
+                        Self::update_param(param, current_value, new_value);
+                    }
+                }
+            }
+            Self::ProposeGovernanceAuthority {
+                new_governance_authority,
+            } => {
+                // A new proposal replaces any proposal that has not been accepted yet
+                sol_strategy.pending_governance_authority = Some(*new_governance_authority);
+
+                emit!(GovernanceAuthorityProposed {
+                    governance_authority: sol_strategy.governance_authority,
+                    pending_governance_authority: *new_governance_authority,
+                });
+            }
+            Self::UpdatePlatformAddress { platform_address } => {
+                emit!(PlatformAddressUpdated {
+                    old_platform_address: sol_strategy.platform_address,
+                    new_platform_address: *platform_address,
+                });
+                sol_strategy.platform_address = *platform_address;
+            }
+            Self::UpdatePlatformFeeSplits {
+                platform_fee_splits,
+            } => {
+                emit!(PlatformFeeSplitsUpdated {
+                    old_platform_fee_splits: sol_strategy.platform_fee_splits.clone(),
+                    new_platform_fee_splits: platform_fee_splits.clone(),
+                });
+                sol_strategy.platform_fee_splits = platform_fee_splits.clone();
+            }
+            Self::UpdateRedemptionParams {
+                allow_redemption,
+                redemption_fee,
+                redemption_cap_per_epoch,
+            } => {
+                if let Some(allow_redemption) = *allow_redemption {
+                    sol_strategy.allow_redemption = allow_redemption;
+                }
+                if let Some(redemption_fee) = *redemption_fee {
+                    sol_strategy.redemption_fee = redemption_fee;
+                }
+                if let Some(redemption_cap_per_epoch) = *redemption_cap_per_epoch {
+                    sol_strategy.redemption_cap_per_epoch = redemption_cap_per_epoch;
+                }
+
+                emit!(RedemptionParamsUpdated {
+                    allow_redemption: sol_strategy.allow_redemption,
+                    redemption_fee: sol_strategy.redemption_fee,
+                    redemption_cap_per_epoch: sol_strategy.redemption_cap_per_epoch,
+                });
+            }
+            Self::SetStakePool { stake_pool } => {
+                emit!(StakePoolUpdated {
+                    old_stake_pool: sol_strategy.stake_pool,
+                    new_stake_pool: *stake_pool,
+                });
+                sol_strategy.stake_pool = *stake_pool;
+            }
+            Self::SetGovernanceDelay { governance_delay } => {
+                Self::update_param(
+                    StrategyParam::GovernanceDelay,
+                    &mut sol_strategy.governance_delay,
+                    *governance_delay,
+                );
+            }
+            Self::SetGuardian { guardian } => {
+                emit!(GuardianUpdated {
+                    old_guardian: sol_strategy.guardian,
+                    new_guardian: *guardian,
+                });
+                sol_strategy.guardian = *guardian;
+            }
+            Self::ConfigureOracle {
+                price_oracle,
+                max_staleness,
+                max_confidence,
+                max_deviation,
+            } => {
+                sol_strategy.price_oracle = *price_oracle;
+                sol_strategy.oracle_max_staleness = *max_staleness;
+                sol_strategy.oracle_max_confidence = *max_confidence;
+                sol_strategy.oracle_max_deviation = *max_deviation;
+
+                emit!(OracleConfigured {
+                    price_oracle: *price_oracle,
+                    max_staleness: *max_staleness,
+                    max_confidence: *max_confidence,
+                    max_deviation: *max_deviation,
+                });
+            }
+            Self::ConfigureTwap {
+                twap_window,
+                min_observation_interval,
+                price_reporter,
+                pool_solxr_vault,
+                pool_sol_vault,
+            } => {
+                let Some(price_observation) = price_observation else {
+                    return err!(Error::PriceObservationRequired);
+                };
+                sol_strategy.twap_window = *twap_window;
+                price_observation.price_reporter = *price_reporter;
+                price_observation.pool_solxr_vault = *pool_solxr_vault;
+                price_observation.pool_sol_vault = *pool_sol_vault;
+                price_observation.min_observation_interval = *min_observation_interval;
+                // Observations of the previous source would skew the new TWAP
+                price_observation.next_index = 0;
+                price_observation.count = 0;
+
+                emit!(TwapConfigured {
+                    twap_window: *twap_window,
+                    min_observation_interval: *min_observation_interval,
+                    price_reporter: *price_reporter,
+                    pool_solxr_vault: *pool_solxr_vault,
+                    pool_sol_vault: *pool_sol_vault,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn update_param(param: StrategyParam, current_value: &mut u64, new_value: u64) {
+        emit!(StrategyParamUpdated {
+            param,
+            old_value: *current_value,
+            new_value,
+        });
+        *current_value = new_value;
+    }
+}
+
+#[error_code]
+enum Error {
+    #[msg("The platform mint fee exceeds the hard ceiling.")]
+    PlatformMintFeeTooHigh,
+
+    #[msg("The max platform mint fee exceeds the hard ceiling.")]
+    MaxPlatformMintFeeTooHigh,
+
+    #[msg("The max mint per wallet must be greater than zero.")]
+    InvalidMaxMintPerWallet,
+
+    #[msg("The minimum premium to NAV ratio exceeds the hard ceiling.")]
+    MinPremiumNavRatioTooHigh,
+
+    #[msg("The NAV growth rate must be greater than zero and at most 100%.")]
+    InvalidNavGrowthRate,
+
+    #[msg("The number of minting rounds cannot drop below the next minting round.")]
+    MintingRoundsBelowNextRound,
+
+    #[msg("The mint duration must be greater than zero and within the maximum duration.")]
+    InvalidMintDuration,
+
+    #[msg("The proposed governance authority must be a new, non-default address.")]
+    InvalidGovernanceAuthority,
+
+    #[msg("The platform address cannot be the default public key.")]
+    InvalidPlatformAddress,
+
+    #[msg("Too many platform fee recipients.")]
+    TooManyFeeSplits,
+
+    #[msg("A fee recipient cannot be the default public key.")]
+    InvalidFeeRecipient,
+
+    #[msg("A fee recipient can only appear once.")]
+    DuplicateFeeRecipient,
+
+    #[msg("Every fee split must have a share greater than zero.")]
+    InvalidFeeSplit,
+
+    #[msg("The fee splits add up to more than 100%.")]
+    FeeSplitsExceedTotal,
+
+    #[msg("The redemption fee exceeds the hard ceiling.")]
+    RedemptionFeeTooHigh,
+
+    #[msg("The governance delay is outside of the allowed bounds.")]
+    InvalidGovernanceDelay,
+
+    #[msg("The guardian cannot be the default public key.")]
+    InvalidGuardian,
+
+    #[msg("The oracle staleness must be greater than zero and within the hard ceiling.")]
+    InvalidMaxStaleness,
+
+    #[msg("The oracle confidence limit exceeds the hard ceiling.")]
+    MaxConfidenceTooHigh,
+
+    #[msg("The oracle deviation band exceeds the hard ceiling.")]
+    MaxDeviationTooHigh,
+
+    #[msg("Both the Solxr and the Sol pool vaults must be provided.")]
+    IncompletePoolVaults,
+
+    #[msg("A price reporter or an AMM pool is required to enable the TWAP.")]
+    MissingPriceSource,
+
+    #[msg(
+        "The observation buffer cannot span the TWAP window at the minimum observation interval."
+    )]
+    WindowExceedsObservationCapacity,
+
+    #[msg("The price observation account is required to configure the TWAP.")]
+    PriceObservationRequired,
+}
@@ -6,14 +6,90 @@ use anchor_lang::{account, InitSpace};
 #[derive(InitSpace)]
 pub struct MintRound {
     pub premium: u64,
+    /// How the Solxr price moves away from `premium` during the round
+    pub curve: PriceCurve,
     pub max_mint_per_wallet: u64,
+    /// Merkle root of the `(address, allocation)` allowlist, None when anyone can mint
+    pub allowlist_root: Option<[u8; 32]>,
     pub solxr_minted: u64,
     pub solxr_available: u64,
     pub start: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum PriceCurve {
+    /// Every Solxr of the round is sold at `premium`
+    Fixed,
+    /// Price rises linearly from `premium` to `max_premium` as `solxr_minted` approaches `solxr_available`
+    Bonding { max_premium: u64 },
+    /// Price falls linearly from `premium` to `floor_premium` over the mint duration
+    TimeDecay { floor_premium: u64 },
+}
+
 impl MintRound {
     pub const SEED_PREFIX: &'static [u8] = b"mint_round";
+
+    /// Price in lamport of the next Solxr minted at `timestamp`
+    pub fn spot_price(&self, timestamp: u64, mint_duration: u64) -> u64 {
+        match self.curve {
+            PriceCurve::Fixed => self.premium,
+            PriceCurve::Bonding { max_premium } => {
+                if self.solxr_available == 0 {
+                    return self.premium;
+                }
+                let minted = self.solxr_minted.min(self.solxr_available);
+                let increase = (max_premium - self.premium) as u128 * minted as u128
+                    / self.solxr_available as u128;
+                self.premium + increase as u64
+            }
+            PriceCurve::TimeDecay { floor_premium } => {
+                let elapsed = timestamp.saturating_sub(self.start);
+                if mint_duration == 0 || elapsed >= mint_duration {
+                    return floor_premium;
+                }
+                let decrease = (self.premium - floor_premium) as u128 * elapsed as u128
+                    / mint_duration as u128;
+                self.premium - decrease as u64
+            }
+        }
+    }
+
+    /// Solxr minted for `sol_amount` lamport at `timestamp`. On a bonding curve the price keeps
+    /// rising while the purchase is minted, so the cost is integrated along the curve.
+    pub fn calculate_solxr_to_mint(
+        &self,
+        sol_amount: u64,
+        timestamp: u64,
+        mint_duration: u64,
+    ) -> u64 {
+        let price = self.spot_price(timestamp, mint_duration) as u128;
+        if let PriceCurve::Bonding { max_premium } = self.curve {
+            let slope = (max_premium - self.premium) as u128;
+            if slope > 0 && self.solxr_minted < self.solxr_available {
+                // (price * q + slope * q^2 / (2 * available)) / 10^9 = sol_amount, solved for q
+                let available = self.solxr_available as u128;
+                let discriminant =
+                    price * price + 2 * slope * sol_amount as u128 * u128::pow(10, 9) / available;
+                let solxr_to_mint = (Self::isqrt(discriminant) - price) * available / slope;
+                return solxr_to_mint as u64;
+            }
+        }
+        let value = sol_amount as u128 * u128::pow(10, 9) / price;
+        value as u64
+    }
+
+    fn isqrt(value: u128) -> u128 {
+        if value < 2 {
+            return value;
+        }
+        let mut x = value;
+        let mut y = x.div_ceil(2);
+        while y < x {
+            x = y;
+            y = (x + value / x) / 2;
+        }
+        x
+    }
 }
 
 #[account]
@@ -21,4 +97,4 @@ impl MintRound {
 pub struct AssociatedRoundAccount {
     /// amount minted in sol
     pub amount_minted: u64,
-}
\ No newline at end of file
+}
@@ -1,4 +1,8 @@
 pub mod sol_strategy;
 pub mod bonds;
 pub mod mint_round;
-pub mod whitelists;
\ No newline at end of file
+pub mod whitelists;
+pub mod price_observation;
+pub mod auction;
+pub mod native_stake;
+pub mod governance_action;
\ No newline at end of file
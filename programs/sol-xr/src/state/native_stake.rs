@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{account, InitSpace};
+
+#[account]
+#[derive(InitSpace)]
+pub struct NativeStake {
+    pub id: u64,
+    /// Stake account funded from the treasury, the treasury PDA is its staker and withdrawer
+    pub stake_account: Pubkey,
+    /// Vote account the stake is delegated to, None until delegated
+    pub vote_account: Option<Pubkey>,
+    /// Lamports of the stake account counted in `SolStrategy.staked_lamports`, rewards included once synced
+    pub lamports: u64,
+    pub deactivated: bool,
+}
+
+impl NativeStake {
+    pub const SEED_PREFIX: &'static [u8] = b"native_stake";
+    pub const STAKE_ACCOUNT_SEED_PREFIX: &'static [u8] = b"stake_account";
+}
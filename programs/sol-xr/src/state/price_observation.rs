@@ -0,0 +1,104 @@
+use crate::constants::MAX_OBSERVATION_DEVIATION;
+use anchor_lang::prelude::*;
+use anchor_lang::{account, InitSpace};
+
+pub const MAX_PRICE_OBSERVATIONS: usize = 32;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PriceObservation {
+    /// Account allowed to push a Solxr price directly
+    pub price_reporter: Option<Pubkey>,
+    /// AMM pool token account holding Solxr
+    pub pool_solxr_vault: Option<Pubkey>,
+    /// AMM pool token account holding wrapped Sol
+    pub pool_sol_vault: Option<Pubkey>,
+    /// Minimum number of seconds between two observations
+    pub min_observation_interval: u64,
+    /// Index the next observation is written to
+    pub next_index: u8,
+    /// Number of observations written, up to `MAX_PRICE_OBSERVATIONS`
+    pub count: u8,
+    pub observations: [Observation; MAX_PRICE_OBSERVATIONS],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Observation {
+    pub timestamp: u64,
+    /// Solxr price in lamport
+    pub price: u64,
+    /// Sum of price * seconds since the first observation
+    pub cumulative_price: u128,
+}
+
+impl PriceObservation {
+    pub const SEED_PREFIX: &'static [u8] = b"price_observation";
+
+    pub fn latest(&self) -> Option<&Observation> {
+        if self.count == 0 {
+            return None;
+        }
+        let index =
+            (self.next_index as usize + MAX_PRICE_OBSERVATIONS - 1) % MAX_PRICE_OBSERVATIONS;
+        Some(&self.observations[index])
+    }
+
+    pub fn push(&mut self, timestamp: u64, price: u64) {
+        let cumulative_price = match self.latest() {
+            Some(latest) => {
+                latest.cumulative_price
+                    + latest.price as u128 * (timestamp - latest.timestamp) as u128
+            }
+            None => 0,
+        };
+        self.observations[self.next_index as usize] = Observation {
+            timestamp,
+            price,
+            cumulative_price,
+        };
+        self.next_index = ((self.next_index as usize + 1) % MAX_PRICE_OBSERVATIONS) as u8;
+        if (self.count as usize) < MAX_PRICE_OBSERVATIONS {
+            self.count += 1;
+        }
+    }
+
+    /// Clamps `price` within `MAX_OBSERVATION_DEVIATION` of the TWAP over `window`, or of the
+    /// latest price while the TWAP isn't available, so that a price manipulated for a single
+    /// observation can only drag the average so far
+    pub fn bounded_price(&self, timestamp: u64, window: u64, price: u64) -> u64 {
+        let Some(latest) = self.latest() else {
+            return price;
+        };
+        let reference = self.twap(timestamp, window).unwrap_or(latest.price);
+        let max_change = (reference as u128 * MAX_OBSERVATION_DEVIATION as u128
+            / u128::pow(10, 9)) as u64;
+        price.clamp(reference - max_change, reference.saturating_add(max_change))
+    }
+
+    /// Time-weighted average price over the `window` seconds before `now`,
+    /// `None` when the stored observations don't reach back to the start of the window
+    pub fn twap(&self, now: u64, window: u64) -> Option<u64> {
+        let latest = self.latest()?;
+        let window_start = now.checked_sub(window)?;
+        if window == 0 || latest.timestamp > now {
+            return None;
+        }
+
+        // Most recent observation at or before the start of the window
+        let start = (0..self.count as usize)
+            .map(|age| {
+                let index = (self.next_index as usize + MAX_PRICE_OBSERVATIONS - 1 - age)
+                    % MAX_PRICE_OBSERVATIONS;
+                &self.observations[index]
+            })
+            .find(|observation| observation.timestamp <= window_start)?;
+
+        let cumulative_at = |observation: &Observation, timestamp: u64| {
+            observation.cumulative_price
+                + observation.price as u128 * (timestamp - observation.timestamp) as u128
+        };
+        let twap =
+            (cumulative_at(latest, now) - cumulative_at(start, window_start)) / window as u128;
+        Some(twap as u64)
+    }
+}
@@ -1,22 +1,48 @@
 use anchor_lang::prelude::*;
 use anchor_lang::{account, InitSpace};
 
+use crate::MAX_PLATFORM_FEE_SPLITS;
+
 #[account]
 #[derive(InitSpace)]
 pub struct SolStrategy {
     pub initial_pool_cap: u64,
+    /// Sol ever invested at 1:1 in the initial pool, never lowered so the pool can't reopen
+    pub initial_pool_invested: u64,
     pub individual_address_cap: u64,
     /// Number of Sol in the treasury
     pub sol_in_treasury: u64,
     /// Number of Sol from issuing bond
     pub sol_from_bond: u64,
+    /// Bumped whenever `sol_from_bond` changes, so an audit of the bond reserves spanning several
+    /// transactions knows it has to start over
+    pub sol_from_bond_updates: u64,
+    /// Solxr minted by the program minus the Solxr burned on redemption
+    pub solxr_issued: u64,
+    /// Lamports moved from the treasury to native stake accounts, rewards included once synced
+    pub staked_lamports: u64,
+    /// SPL stake pool the treasury deposits into for liquid staking
+    pub stake_pool: Option<Pubkey>,
+    /// Value of the stake pool tokens held by the treasury at the pool exchange rate, refreshed on sync
+    pub stake_pool_lamports: u64,
 
     // Governance-controlled parameters
     /// An account with governance authority responsible for executing all governance-controlled instructions
     pub governance_authority: Pubkey,
+    /// Account proposed to take over governance authority, switched only once it accepts
+    pub pending_governance_authority: Option<Pubkey>,
+    /// Seconds a sensitive governance action waits in the queue before it can be executed
+    pub governance_delay: u64,
+    /// Account allowed to pause subsystems, it can neither unpause nor change parameters
+    pub guardian: Option<Pubkey>,
+    /// Bitflags of the paused subsystems, see the `PAUSE_*` constants
+    pub paused: u8,
 
     /// Designated Account for platform
     pub platform_address: Pubkey,
+    /// Additional platform fee recipients, the remainder of the fee goes to `platform_address`
+    #[max_len(MAX_PLATFORM_FEE_SPLITS)]
+    pub platform_fee_splits: Vec<FeeSplit>,
 
     // Minting New Shares
     /// Allow Solxr to be minted
@@ -38,12 +64,78 @@ pub struct SolStrategy {
     /// Duration for minting
     pub mint_duration: u64,
 
+    // Market value oracle
+    /// Pyth-style Solxr/Sol price account used to check the market value when opening a round
+    pub price_oracle: Option<Pubkey>,
+    /// Maximum age of the oracle price in seconds
+    pub oracle_max_staleness: u64,
+    /// Maximum oracle confidence interval relative to the price in lamport i.e. 1 sol (1_000_000_000 lamport) = 100%
+    pub oracle_max_confidence: u64,
+    /// Maximum deviation of the supplied market value from the oracle price in lamport i.e. 1 sol (1_000_000_000 lamport) = 100%
+    pub oracle_max_deviation: u64,
+    /// Window in seconds of the Solxr TWAP used for the premium to NAV check, zero disables it
+    pub twap_window: u64,
+
+    // Redeeming Solxr at NAV
+    /// Allow Solxr to be redeemed for Sol in the treasury
+    pub allow_redemption: bool,
+    /// Fee kept in the treasury for every redemption in lamport i.e. 1 sol (1_000_000_000 lamport) = 100%
+    pub redemption_fee: u64,
+    /// Maximum lamports that can be redeemed from the treasury in a single epoch
+    pub redemption_cap_per_epoch: u64,
+    /// Epoch the redemption counter belongs to
+    pub redemption_epoch: u64,
+    /// Lamports redeemed from the treasury during `redemption_epoch`
+    pub redeemed_in_epoch: u64,
+
     /// Next bond id
     pub next_bond_id: u64,
     /// Next bond id
     pub next_whitelist_id: u64,
+    /// Next native stake id
+    pub next_stake_id: u64,
+    /// Next timelocked governance action id
+    pub next_governance_action_id: u64,
 }
 
 impl SolStrategy {
     pub const SEED_PREFIX: &'static [u8] = b"sol_strategy";
+
+    pub const PAUSE_INVEST: u8 = 1 << 0;
+    pub const PAUSE_MINT_ROUNDS: u8 = 1 << 1;
+    pub const PAUSE_BONDS: u8 = 1 << 2;
+    pub const PAUSE_WHITELISTS: u8 = 1 << 3;
+    pub const PAUSE_CONVERSIONS: u8 = 1 << 4;
+    pub const PAUSE_REDEMPTIONS: u8 = 1 << 5;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_INVEST
+        | Self::PAUSE_MINT_ROUNDS
+        | Self::PAUSE_BONDS
+        | Self::PAUSE_WHITELISTS
+        | Self::PAUSE_CONVERSIONS
+        | Self::PAUSE_REDEMPTIONS;
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    /// Sol backing the Solxr supply, idle in the treasury, staked or in the stake pool
+    pub fn sol_backing(&self) -> u64 {
+        self.sol_in_treasury + self.staked_lamports + self.stake_pool_lamports
+    }
+
+    /// Net asset value of one Solxr in lamport, zero while there is no Solxr supply
+    pub fn nav(&self, token_supply: u64) -> u64 {
+        if token_supply == 0 {
+            return 0;
+        }
+        let nav = self.sol_backing() as u128 * u128::pow(10, 9) / token_supply as u128;
+        nav as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, PartialEq, Eq, Debug)]
+pub struct FeeSplit {
+    pub recipient: Pubkey,
+    /// Share of the platform fee in basis points i.e. 10_000 = 100%
+    pub basis_points: u16,
 }
@@ -1,15 +1,24 @@
 use anchor_lang::prelude::*;
 use anchor_lang::{account, InitSpace};
 
+use crate::state::auction::DutchAuction;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Whitelist {
     pub maturity: u64,
     pub expiration: u64,
+    pub supply: u64,
     pub price: u64,
     pub max_mint_per_wallet: u64,
     pub start_time: u64,
     pub end_time: u64,
+    /// Descending price charged instead of `price` between `start_time` and `end_time`
+    pub auction: Option<DutchAuction>,
+    /// Merkle root of the `(address, allocation)` allowlist, None when anyone can buy
+    pub allowlist_root: Option<[u8; 32]>,
+    /// Converted editions are locked in the SolStrategy escrow instead of burned
+    pub escrow_on_conversion: bool,
     pub next_edition_number: u64,
     #[max_len(248)]
     pub next_edition_marker: String,
@@ -17,6 +26,14 @@ pub struct Whitelist {
 
 impl Whitelist {
     pub const SEED_PREFIX: &'static [u8] = b"whitelist";
+
+    /// Price of one edition bought at `timestamp`
+    pub fn current_price(&self, timestamp: u64) -> u64 {
+        match &self.auction {
+            Some(auction) => auction.price_at(self.start_time, self.end_time, timestamp),
+            None => self.price,
+        }
+    }
 }
 
 #[account]
@@ -33,8 +50,17 @@ impl WhitelistRecord {
 #[account]
 #[derive(InitSpace)]
 pub struct WhitelistNFTRecord {
+    /// Sol paid for the edition, minted as Solxr when it is converted
+    pub paid_price: u64,
     pub converted: bool,
 }
 impl WhitelistNFTRecord {
     pub const SEED_PREFIX: &'static [u8] = b"whitelist_nft_record";
+
+    pub fn new(paid_price: u64) -> Self {
+        Self {
+            paid_price,
+            converted: false,
+        }
+    }
 }
\ No newline at end of file
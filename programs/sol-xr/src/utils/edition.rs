@@ -0,0 +1,250 @@
+use {
+    anchor_lang::{prelude::*, solana_program::program_pack::Pack, system_program},
+    anchor_spl::{
+        associated_token::{self, get_associated_token_address},
+        metadata::mpl_token_metadata::{
+            instructions::{
+                MintNewEditionFromMasterEditionViaTokenCpi,
+                MintNewEditionFromMasterEditionViaTokenCpiAccounts,
+                MintNewEditionFromMasterEditionViaTokenInstructionArgs,
+            },
+            types::MintNewEditionFromMasterEditionViaTokenArgs,
+        },
+        token::{self, spl_token, InitializeMint2, MintTo},
+    },
+};
+
+/// Number of remaining accounts describing one additional print edition:
+/// mint, buyer token account, metadata, edition and edition marker
+pub const PRINT_EDITION_ACCOUNTS: usize = 5;
+
+/// Accounts of the master edition shared by every print minted in one purchase
+pub struct MasterEditionAccounts<'a, 'info> {
+    pub buyer: &'a AccountInfo<'info>,
+    /// SolStrategy PDA owning the master edition token
+    pub owner: &'a AccountInfo<'info>,
+    pub master_token_account: &'a AccountInfo<'info>,
+    pub master_metadata: &'a AccountInfo<'info>,
+    pub master_edition: &'a AccountInfo<'info>,
+    pub metadata_program: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+}
+
+/// Accounts of a single print edition owned by the buyer
+pub struct PrintEditionAccounts<'a, 'info> {
+    pub mint: &'a AccountInfo<'info>,
+    pub token_account: &'a AccountInfo<'info>,
+    pub metadata: &'a AccountInfo<'info>,
+    pub edition: &'a AccountInfo<'info>,
+    pub edition_mark_pda: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> PrintEditionAccounts<'a, 'info> {
+    /// Splits remaining accounts into `PRINT_EDITION_ACCOUNTS` sized groups
+    pub fn from_remaining_accounts(
+        remaining_accounts: &'a [AccountInfo<'info>],
+        count: usize,
+    ) -> Result<Vec<Self>> {
+        require!(
+            remaining_accounts.len() == count * PRINT_EDITION_ACCOUNTS,
+            Error::PrintEditionAccountsMismatch
+        );
+        Ok(remaining_accounts
+            .chunks_exact(PRINT_EDITION_ACCOUNTS)
+            .map(|accounts| Self {
+                mint: &accounts[0],
+                token_account: &accounts[1],
+                metadata: &accounts[2],
+                edition: &accounts[3],
+                edition_mark_pda: &accounts[4],
+            })
+            .collect())
+    }
+}
+
+impl<'a, 'info> MasterEditionAccounts<'a, 'info> {
+    /// Mints the single token of the print mint to the buyer and prints it as `edition`
+    pub fn print(
+        &self,
+        print: &PrintEditionAccounts<'_, 'info>,
+        edition: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        token::mint_to(
+            CpiContext::new(
+                self.token_program.clone(),
+                MintTo {
+                    mint: print.mint.clone(),
+                    to: print.token_account.clone(),
+                    authority: self.buyer.clone(),
+                },
+            ),
+            1,
+        )?;
+
+        let cpi_accounts = MintNewEditionFromMasterEditionViaTokenCpiAccounts {
+            new_metadata: print.metadata,
+            new_edition: print.edition,
+            master_edition: self.master_edition,
+            new_mint: print.mint,
+            edition_mark_pda: print.edition_mark_pda,
+            new_mint_authority: self.buyer,
+            payer: self.buyer,
+            token_account_owner: self.owner,
+            token_account: self.master_token_account,
+            new_metadata_update_authority: self.buyer,
+            metadata: self.master_metadata,
+            token_program: self.token_program,
+            system_program: self.system_program,
+            rent: Some(self.rent),
+        };
+
+        let instruction_args = MintNewEditionFromMasterEditionViaTokenInstructionArgs {
+            mint_new_edition_from_master_edition_via_token_args:
+                MintNewEditionFromMasterEditionViaTokenArgs { edition },
+        };
+
+        MintNewEditionFromMasterEditionViaTokenCpi::new(
+            self.metadata_program,
+            cpi_accounts,
+            instruction_args,
+        )
+        .invoke_signed(signer_seeds)?;
+        Ok(())
+    }
+
+    /// Creates the `[collection, edition]` mint PDA and the buyer token account of an
+    /// additional print edition, then prints it
+    pub fn create_and_print(
+        &self,
+        print: &PrintEditionAccounts<'_, 'info>,
+        collection: Pubkey,
+        edition: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let edition_bytes = edition.to_le_bytes();
+        let (mint_address, mint_bump) =
+            Pubkey::find_program_address(&[collection.as_ref(), &edition_bytes], &crate::ID);
+        require_keys_eq!(
+            print.mint.key(),
+            mint_address,
+            Error::InvalidEditionMint
+        );
+        require_keys_eq!(
+            print.token_account.key(),
+            get_associated_token_address(self.buyer.key, &mint_address),
+            Error::InvalidEditionTokenAccount
+        );
+
+        let mint_seeds: &[&[u8]] = &[collection.as_ref(), &edition_bytes, &[mint_bump]];
+        create_pda_account(
+            self.buyer,
+            self.system_program,
+            print.mint,
+            spl_token::state::Mint::LEN,
+            self.token_program.key,
+            &[mint_seeds],
+        )?;
+        token::initialize_mint2(
+            CpiContext::new(
+                self.token_program.clone(),
+                InitializeMint2 {
+                    mint: print.mint.clone(),
+                },
+            ),
+            0,
+            self.buyer.key,
+            Some(self.buyer.key),
+        )?;
+
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.clone(),
+            associated_token::Create {
+                payer: self.buyer.clone(),
+                associated_token: print.token_account.clone(),
+                authority: self.buyer.clone(),
+                mint: print.mint.clone(),
+                system_program: self.system_program.clone(),
+                token_program: self.token_program.clone(),
+            },
+        ))?;
+
+        self.print(print, edition, signer_seeds)
+    }
+}
+
+/// Creates a PDA owned by `owner` with `space` bytes, paid by `payer`. The address is
+/// predictable, so it may already hold lamports: it is then topped up to rent exemption,
+/// allocated and assigned instead of failing like `create_account` would.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            rent_exempt,
+            space as u64,
+            owner,
+        );
+    }
+
+    let top_up = rent_exempt.saturating_sub(account.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
+
+#[error_code]
+enum Error {
+    #[msg("The remaining accounts don't match the number of additional editions to mint.")]
+    PrintEditionAccountsMismatch,
+    #[msg("The print edition mint doesn't match the collection and edition number.")]
+    InvalidEditionMint,
+    #[msg("The print edition token account isn't the buyer's associated token account.")]
+    InvalidEditionTokenAccount,
+}
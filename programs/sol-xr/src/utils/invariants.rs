@@ -0,0 +1,92 @@
+use {
+    crate::{
+        events::{Invariant, InvariantViolated, PauseUpdated},
+        state::sol_strategy::SolStrategy,
+    },
+    anchor_lang::prelude::*,
+};
+
+/// Treasury lamports must cover the recorded Sol and the treasury rent exempt minimum
+pub fn treasury_coverage(
+    sol_strategy: &SolStrategy,
+    treasury_lamports: u64,
+) -> Result<Option<InvariantViolated>> {
+    let required =
+        sol_strategy.sol_in_treasury + sol_strategy.sol_from_bond + Rent::get()?.minimum_balance(0);
+    Ok((treasury_lamports < required).then_some(InvariantViolated {
+        invariant: Invariant::TreasuryCoverage,
+        expected: required,
+        actual: treasury_lamports,
+    }))
+}
+
+/// Solxr supply can't exceed the Solxr issued by the program, holders can still burn their own
+pub fn solxr_supply(sol_strategy: &SolStrategy, token_supply: u64) -> Option<InvariantViolated> {
+    (token_supply > sol_strategy.solxr_issued).then_some(InvariantViolated {
+        invariant: Invariant::SolxrSupply,
+        expected: sol_strategy.solxr_issued,
+        actual: token_supply,
+    })
+}
+
+/// Checked at the end of every handler moving Sol in or out of the treasury
+pub fn check_treasury(sol_strategy: &SolStrategy, treasury_lamports: u64) -> Result<()> {
+    if let Some(violation) = treasury_coverage(sol_strategy, treasury_lamports)? {
+        msg!(
+            "Treasury holds {} lamports, {} required",
+            violation.actual,
+            violation.expected
+        );
+        return err!(Error::TreasuryUndercollateralized);
+    }
+    Ok(())
+}
+
+/// Checked at the end of every handler minting or burning Solxr
+pub fn check_solxr_supply(sol_strategy: &SolStrategy, token_supply: u64) -> Result<()> {
+    if let Some(violation) = solxr_supply(sol_strategy, token_supply) {
+        msg!(
+            "Solxr supply is {}, {} issued",
+            violation.actual,
+            violation.expected
+        );
+        return err!(Error::SolxrSupplyMismatch);
+    }
+    Ok(())
+}
+
+/// Emits every violation found by a permissionless check, then fails the transaction or,
+/// when `pause_on_violation` is set, pauses the whole protocol instead
+pub fn report_violations(
+    sol_strategy: &mut Account<SolStrategy>,
+    violations: Vec<InvariantViolated>,
+    pause_on_violation: bool,
+) -> Result<()> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+    for violation in violations {
+        emit!(violation);
+    }
+    if !pause_on_violation {
+        return err!(Error::InvariantViolated);
+    }
+
+    emit!(PauseUpdated {
+        authority: sol_strategy.key(),
+        old_paused: sol_strategy.paused,
+        new_paused: SolStrategy::PAUSE_ALL,
+    });
+    sol_strategy.paused = SolStrategy::PAUSE_ALL;
+    Ok(())
+}
+
+#[error_code]
+enum Error {
+    #[msg("The treasury doesn't cover the Sol recorded in the SolStrategy.")]
+    TreasuryUndercollateralized,
+    #[msg("The Solxr supply exceeds the Solxr issued by the program.")]
+    SolxrSupplyMismatch,
+    #[msg("A solvency invariant of the SolStrategy is violated.")]
+    InvariantViolated,
+}
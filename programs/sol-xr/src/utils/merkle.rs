@@ -0,0 +1,56 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+/// Allocation of the buyer in an allowlist and the proof of its leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllowlistProof {
+    pub allocation: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Leaf of an allowlist Merkle tree, `sha256(address || allocation)` with the allocation little endian
+pub fn allowlist_leaf(address: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[address.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+/// Walks the proof up to the root, each pair of nodes is hashed in sorted order
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+/// Returns the allocation of `buyer` when an allowlist root is set, None when anyone can buy
+pub fn check_allowlist(
+    root: Option<[u8; 32]>,
+    buyer: &Pubkey,
+    allowlist: Option<&AllowlistProof>,
+) -> Result<Option<u64>> {
+    let Some(root) = root else {
+        return Ok(None);
+    };
+    let Some(allowlist) = allowlist else {
+        return err!(Error::AllowlistProofRequired);
+    };
+    require!(
+        verify_proof(
+            &allowlist.proof,
+            &root,
+            allowlist_leaf(buyer, allowlist.allocation)
+        ),
+        Error::InvalidAllowlistProof
+    );
+    Ok(Some(allowlist.allocation))
+}
+
+#[error_code]
+enum Error {
+    #[msg("This sale is gated by an allowlist, a Merkle proof is required.")]
+    AllowlistProofRequired,
+    #[msg("The Merkle proof doesn't match the allowlist root for this buyer and allocation.")]
+    InvalidAllowlistProof,
+}
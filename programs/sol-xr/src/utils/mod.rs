@@ -0,0 +1,5 @@
+pub mod oracle;
+pub mod edition;
+pub mod merkle;
+pub mod stake_pool;
+pub mod invariants;
\ No newline at end of file
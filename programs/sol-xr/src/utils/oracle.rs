@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+/// Magic number at the start of every Pyth-style account
+pub const PRICE_ACCOUNT_MAGIC: u32 = 0xa1b2c3d4;
+/// Account type of a price account
+pub const PRICE_ACCOUNT_TYPE: u32 = 3;
+/// Aggregate price status when the price is being actively published
+pub const PRICE_STATUS_TRADING: u32 = 1;
+
+// Byte offsets in the Pyth v2 price account layout
+const MAGIC_OFFSET: usize = 0;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPONENT_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGGREGATE_PRICE_OFFSET: usize = 208;
+const AGGREGATE_CONF_OFFSET: usize = 216;
+const AGGREGATE_STATUS_OFFSET: usize = 224;
+pub const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// Aggregate price read from a Pyth-style price account, here the Solxr/Sol price
+pub struct PriceFeed {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PriceFeed {
+    pub fn load(price_account: &AccountInfo) -> Result<Self> {
+        let data = price_account.try_borrow_data()?;
+        require!(
+            data.len() >= PRICE_ACCOUNT_MIN_LEN,
+            Error::InvalidPriceAccount
+        );
+        require!(
+            read_u32(&data, MAGIC_OFFSET) == PRICE_ACCOUNT_MAGIC,
+            Error::InvalidPriceAccount
+        );
+        require!(
+            read_u32(&data, ACCOUNT_TYPE_OFFSET) == PRICE_ACCOUNT_TYPE,
+            Error::InvalidPriceAccount
+        );
+        require!(
+            read_u32(&data, AGGREGATE_STATUS_OFFSET) == PRICE_STATUS_TRADING,
+            Error::PriceNotTrading
+        );
+
+        Ok(Self {
+            price: read_u64(&data, AGGREGATE_PRICE_OFFSET) as i64,
+            conf: read_u64(&data, AGGREGATE_CONF_OFFSET),
+            exponent: read_u32(&data, EXPONENT_OFFSET) as i32,
+            publish_time: read_u64(&data, TIMESTAMP_OFFSET) as i64,
+        })
+    }
+
+    /// Price in lamport per Solxr after checking it is fresh and its confidence interval is tight enough
+    pub fn checked_price(
+        &self,
+        current_time: i64,
+        max_staleness: u64,
+        max_confidence: u64,
+    ) -> Result<u64> {
+        require!(
+            current_time.saturating_sub(self.publish_time) <= max_staleness as i64,
+            Error::StalePrice
+        );
+        require!(self.price > 0, Error::InvalidPrice);
+
+        let price = Self::scale_to_lamports(self.price as u128, self.exponent)?;
+        require!(price > 0, Error::InvalidPrice);
+        let conf = Self::scale_to_lamports(self.conf as u128, self.exponent)?;
+
+        // Confidence interval relative to the price i.e. 1 sol (1_000_000_000 lamport) = 100%
+        let confidence_ratio = conf * u128::pow(10, 9) / price;
+        require!(
+            confidence_ratio <= max_confidence as u128,
+            Error::PriceConfidenceTooWide
+        );
+        require!(price <= u64::MAX as u128, Error::InvalidPrice);
+
+        Ok(price as u64)
+    }
+
+    fn scale_to_lamports(value: u128, exponent: i32) -> Result<u128> {
+        let scale = exponent.saturating_add(9);
+        let factor = 10u128.checked_pow(scale.unsigned_abs());
+        let scaled = if scale >= 0 {
+            factor.and_then(|factor| value.checked_mul(factor))
+        } else {
+            factor.and_then(|factor| value.checked_div(factor))
+        };
+        scaled.ok_or(error!(Error::InvalidPrice))
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[error_code]
+enum Error {
+    #[msg("The provided account is not a valid price account.")]
+    InvalidPriceAccount,
+    #[msg("The oracle price is not currently trading.")]
+    PriceNotTrading,
+    #[msg("The oracle price is older than the allowed staleness.")]
+    StalePrice,
+    #[msg("The oracle confidence interval is too wide.")]
+    PriceConfidenceTooWide,
+    #[msg("The oracle price is invalid.")]
+    InvalidPrice,
+}
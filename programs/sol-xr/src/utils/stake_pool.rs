@@ -0,0 +1,150 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        stake, system_program, sysvar,
+    },
+};
+
+/// SPL stake pool program
+pub const STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+/// Account type of an initialized stake pool
+pub const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+/// Seed of the pool withdraw authority, which mints the pool tokens
+pub const WITHDRAW_AUTHORITY_SEED: &[u8] = b"withdraw";
+
+// Instruction tags of the stake pool program
+const DEPOSIT_SOL_WITH_SLIPPAGE: u8 = 25;
+const WITHDRAW_SOL_WITH_SLIPPAGE: u8 = 26;
+
+// Byte offsets in the stake pool account layout
+const ACCOUNT_TYPE_OFFSET: usize = 0;
+const POOL_MINT_OFFSET: usize = 162;
+const TOTAL_LAMPORTS_OFFSET: usize = 258;
+const POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+pub const STAKE_POOL_MIN_LEN: usize = 274;
+
+/// Exchange rate read from an SPL stake pool account
+pub struct StakePoolState {
+    pub pool_mint: Pubkey,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+}
+
+impl StakePoolState {
+    pub fn load(stake_pool: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *stake_pool.owner,
+            STAKE_POOL_PROGRAM_ID,
+            Error::InvalidStakePoolAccount
+        );
+        let data = stake_pool.try_borrow_data()?;
+        require!(
+            data.len() >= STAKE_POOL_MIN_LEN,
+            Error::InvalidStakePoolAccount
+        );
+        require!(
+            data[ACCOUNT_TYPE_OFFSET] == STAKE_POOL_ACCOUNT_TYPE,
+            Error::InvalidStakePoolAccount
+        );
+
+        Ok(Self {
+            pool_mint: Pubkey::try_from(&data[POOL_MINT_OFFSET..POOL_MINT_OFFSET + 32]).unwrap(),
+            total_lamports: read_u64(&data, TOTAL_LAMPORTS_OFFSET),
+            pool_token_supply: read_u64(&data, POOL_TOKEN_SUPPLY_OFFSET),
+        })
+    }
+
+    /// Lamports `pool_tokens` are worth at the pool exchange rate
+    pub fn lamports_for(&self, pool_tokens: u64) -> u64 {
+        if self.pool_token_supply == 0 {
+            return 0;
+        }
+        let lamports =
+            pool_tokens as u128 * self.total_lamports as u128 / self.pool_token_supply as u128;
+        lamports as u64
+    }
+}
+
+/// Accounts of a stake pool used to deposit or withdraw Sol
+pub struct StakePoolKeys {
+    pub stake_pool: Pubkey,
+    pub withdraw_authority: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub pool_mint: Pubkey,
+}
+
+/// Deposits `lamports` from `from` into the pool reserve, minting pool tokens to `pool_token_account`
+pub fn deposit_sol(
+    keys: &StakePoolKeys,
+    from: &Pubkey,
+    pool_token_account: &Pubkey,
+    lamports: u64,
+    min_pool_tokens_out: u64,
+) -> Instruction {
+    Instruction {
+        program_id: STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(keys.stake_pool, false),
+            AccountMeta::new_readonly(keys.withdraw_authority, false),
+            AccountMeta::new(keys.reserve_stake, false),
+            AccountMeta::new(*from, true),
+            AccountMeta::new(*pool_token_account, false),
+            AccountMeta::new(keys.manager_fee_account, false),
+            // no referrer, the referral fee goes back to the depositor
+            AccountMeta::new(*pool_token_account, false),
+            AccountMeta::new(keys.pool_mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: instruction_data(DEPOSIT_SOL_WITH_SLIPPAGE, lamports, min_pool_tokens_out),
+    }
+}
+
+/// Burns `pool_tokens` owned by `authority` and withdraws their value from the pool reserve to `to`
+pub fn withdraw_sol(
+    keys: &StakePoolKeys,
+    authority: &Pubkey,
+    pool_token_account: &Pubkey,
+    to: &Pubkey,
+    pool_tokens: u64,
+    min_lamports_out: u64,
+) -> Instruction {
+    Instruction {
+        program_id: STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(keys.stake_pool, false),
+            AccountMeta::new_readonly(keys.withdraw_authority, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*pool_token_account, false),
+            AccountMeta::new(keys.reserve_stake, false),
+            AccountMeta::new(*to, false),
+            AccountMeta::new(keys.manager_fee_account, false),
+            AccountMeta::new(keys.pool_mint, false),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(sysvar::stake_history::ID, false),
+            AccountMeta::new_readonly(stake::program::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: instruction_data(WITHDRAW_SOL_WITH_SLIPPAGE, pool_tokens, min_lamports_out),
+    }
+}
+
+fn instruction_data(tag: u8, amount: u64, min_out: u64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(17);
+    data.push(tag);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&min_out.to_le_bytes());
+    data
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[error_code]
+enum Error {
+    #[msg("The provided account is not an initialized SPL stake pool.")]
+    InvalidStakePoolAccount,
+}
@@ -1,17 +1,35 @@
 import {describe, it} from 'node:test';
 import * as anchor from '@coral-xyz/anchor';
-import {Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, Transaction} from '@solana/web3.js';
+import {
+    Authorized,
+    Keypair,
+    LAMPORTS_PER_SOL,
+    PublicKey,
+    StakeProgram,
+    SystemProgram,
+    Transaction,
+    TransactionInstruction,
+    VoteInit,
+    VoteProgram
+} from '@solana/web3.js';
 import {BankrunProvider} from 'anchor-bankrun';
 import {Clock, startAnchor} from 'solana-bankrun';
 import {SolXr} from "../target/types/sol_xr";
 import {expect,} from "chai";
+import {createHash} from "crypto";
 import devKey from '../dev.json'
 import {
     createTransferInstruction,
     getAssociatedTokenAddress,
     createAssociatedTokenAccountInstruction,
+    createInitializeMint2Instruction,
     getAccount,
-    getMint
+    getMint,
+    MINT_SIZE,
+    TOKEN_PROGRAM_ID,
+    ACCOUNT_SIZE,
+    AccountLayout,
+    NATIVE_MINT
 } from '@solana/spl-token';
 import {
     deserializeEdition,
@@ -26,6 +44,7 @@ const {ComputeBudgetProgram} = require('@solana/web3.js');
 const IDL = require('../target/idl/sol_xr.json');
 const PROGRAM_ID = new PublicKey(IDL.address);
 const METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+const STAKE_POOL_PROGRAM_ID = new PublicKey('SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy');
 
 describe("sol-xr", async () => {
     const context = await startAnchor(
@@ -33,6 +52,7 @@ describe("sol-xr", async () => {
         [
             {name: 'sol_xr', programId: PROGRAM_ID},
             {name: 'token_metadata', programId: METADATA_PROGRAM_ID},
+            {name: 'spl_stake_pool', programId: STAKE_POOL_PROGRAM_ID},
         ],
         [],
     );
@@ -40,6 +60,7 @@ describe("sol-xr", async () => {
     const provider = new BankrunProvider(context);
     const providerKeypair = provider.wallet as anchor.Wallet;
     const program = new anchor.Program<SolXr>(IDL, provider);
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
 
     const initialPoolCap = 10_000 * LAMPORTS_PER_SOL;
     const individualAddressCap = 100 * LAMPORTS_PER_SOL;
@@ -60,6 +81,11 @@ describe("sol-xr", async () => {
         program.programId
     );
 
+    const [priceObservationPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("price_observation")],
+        program.programId
+    );
+
     /// Initialize Solxr Token and program parameters
     async function initializeToken(governance_authority: Keypair, initialPoolCap: number, individualAddressCap: number) {
         await program
@@ -152,6 +178,7 @@ describe("sol-xr", async () => {
     let now = Date.now();
     const whitelistMaturity = new anchor.BN(now + 3600);
     const whitelistExpiration = new anchor.BN(now + 7200);
+    const whitelistSupply = new anchor.BN(1_000);
     const whitelistMaxMintPerWallet = new anchor.BN(1);
     const whitelistStartTime = new anchor.BN(now);
     const whitelistEndTime = new anchor.BN(now + 600);
@@ -179,9 +206,13 @@ describe("sol-xr", async () => {
                 price,
                 maturity,
                 expiration,
+                new anchor.BN(1_000),
                 maxMintPerWallet,
                 startTime,
-                endTime
+                endTime,
+                false,
+                null,
+                null,
             )
                 .accounts({governanceAuthority: badActor.publicKey})
                 .signers([badActor])
@@ -204,9 +235,13 @@ describe("sol-xr", async () => {
                 price,
                 whitelistMaturity,
                 whitelistExpiration,
+                whitelistSupply,
                 whitelistMaxMintPerWallet,
                 whitelistStartTime,
-                whitelistEndTime
+                whitelistEndTime,
+                false,
+                null,
+                null,
             )
                 .accounts({governanceAuthority: dev.publicKey})
                 .signers([dev])
@@ -230,13 +265,17 @@ describe("sol-xr", async () => {
             expect(whitelist.maturity.toNumber()).equal(whitelistMaturity.toNumber());
             expect(whitelist.expiration.toNumber()).equal(whitelistExpiration.toNumber());
             expect(whitelist.price.toNumber()).equal(price.toNumber());
+            expect(whitelist.supply.toNumber()).equal(whitelistSupply.toNumber());
             expect(whitelist.maxMintPerWallet.toNumber()).equal(whitelistMaxMintPerWallet.toNumber());
             expect(whitelist.startTime.toNumber()).equal(whitelistStartTime.toNumber());
             expect(whitelist.endTime.toNumber()).equal(whitelistEndTime.toNumber());
             expect(whitelist.nextEditionNumber.toNumber()).equal(1);
             expect(whitelist.nextEditionNumber.toNumber()).equal(1);
             expect(Number(edition.supply)).equal(0);
-            expect(edition.maxSupply.__option).equal("None");
+            expect(edition.maxSupply.__option).equal("Some");
+            if (edition.maxSupply.__option === 'Some') {
+                expect(Number(edition.maxSupply.value)).equal(whitelistSupply.toNumber());
+            }
         }
     });
     // Key pairs to be used for further test
@@ -361,7 +400,7 @@ describe("sol-xr", async () => {
                 );
                 tx.add(
                     await program.methods
-                        .buyWhitelist(new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1))
+                        .buyWhitelist(new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1), new anchor.BN(1), null)
                         .accounts({buyer: buyer.publicKey})
                         .instruction()
                 );
@@ -399,7 +438,7 @@ describe("sol-xr", async () => {
                 expect(edition.parent).equal(whitelistEditionPDA.toBase58(), `parent of mint edition should be ${whitelistNFTPDA.toBase58()}`)
             } else {
                 try {
-                    await program.methods.buyWhitelist(new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1))
+                    await program.methods.buyWhitelist(new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1), new anchor.BN(1), null)
                         .accounts({buyer: buyer.publicKey})
                         .signers([buyer])
                         .rpc();
@@ -477,7 +516,7 @@ describe("sol-xr", async () => {
                 buyer: firstWBuyer,
                 params: {
                     edition: "1",
-                    errorCode: "WhitelistClaimed"
+                    errorCode: "InvalidTokenAmount"
                 },
                 expectedValue: {
                     newSolxrBalance: whitelist.price.toNumber(),
@@ -517,7 +556,7 @@ describe("sol-xr", async () => {
                 buyer: lateWBuyer,
                 params: {
                     edition: "2",
-                    errorCode: "WhitelistClaimed"
+                    errorCode: "InvalidTokenAmount"
                 },
                 expectedValue: {
                     newSolxrBalance: whitelist.price.toNumber(),
@@ -545,6 +584,7 @@ describe("sol-xr", async () => {
                 await program.methods.convertWhitelist(
                     new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1),
                     new anchor.BN(params.edition),
+                    new anchor.BN(0),
                 )
                     .accounts({buyer: buyer.publicKey})
                     .signers([buyer])
@@ -564,7 +604,7 @@ describe("sol-xr", async () => {
 
                 let solxr = await getMint(provider.connection, tokenPDA)
 
-                expect(Number(buyerWhitelistTokenAccountInfo.amount)).equal(1, `buyer whitelist nft account amount should be 0`)
+                expect(Number(buyerWhitelistTokenAccountInfo.amount)).equal(0, `buyer whitelist nft account amount should be 0`)
                 expect(Number(solxrTokenAccountInfo.amount)).equal(expectedValue.newSolxrBalance, `buyer solxr account amount should be ${expectedValue.newSolxrBalance}`)
                 expect(Number(solxr.supply)).equal(expectedValue.newSolxrSupply, `buyer solxr account amount should be ${expectedValue.newSolxrBalance}`)
             } else {
@@ -572,6 +612,7 @@ describe("sol-xr", async () => {
                     await program.methods.convertWhitelist(
                         new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1),
                         new anchor.BN(params.edition),
+                        new anchor.BN(0),
                     )
                         .accounts({buyer: buyer.publicKey})
                         .signers([buyer])
@@ -618,7 +659,7 @@ describe("sol-xr", async () => {
             );
             tx.add(
                 await program.methods
-                    .buyWhitelist(new anchor.BN(whitelistEdition))
+                    .buyWhitelist(new anchor.BN(whitelistEdition), new anchor.BN(1), null)
                     .accounts({buyer: investor.publicKey})
                     .instruction()
             );
@@ -659,6 +700,7 @@ describe("sol-xr", async () => {
             await program.methods.convertWhitelist(
                 new anchor.BN(whitelistEdition),
                 new anchor.BN(childEdition),
+                new anchor.BN(0),
             )
                 .accounts({buyer: key.publicKey})
                 .signers([key])
@@ -673,7 +715,7 @@ describe("sol-xr", async () => {
             const badActor = Keypair.generate();
             await fundAccount(badActor, 5000)
 
-            await program.methods.openMintRound(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL))
+            await program.methods.openMintRound(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), {fixed: {}}, null)
                 .accounts({governanceAuthority: badActor.publicKey})
                 .signers([badActor])
                 .rpc();
@@ -743,7 +785,7 @@ describe("sol-xr", async () => {
             console.log(`When ${desc}`)
 
             if (shouldSucceed) {
-                await program.methods.openMintRound(new anchor.BN(params.roundID), new anchor.BN(params.marketValue))
+                await program.methods.openMintRound(new anchor.BN(params.roundID), new anchor.BN(params.marketValue), {fixed: {}}, null)
                     .accounts({governanceAuthority: dev.publicKey})
                     .signers([dev])
                     .rpc();
@@ -766,7 +808,7 @@ describe("sol-xr", async () => {
                 solxrAvailable = mintRound.solxrAvailable.toNumber();
             } else {
                 try {
-                    await program.methods.openMintRound(new anchor.BN(params.roundID), new anchor.BN(params.marketValue))
+                    await program.methods.openMintRound(new anchor.BN(params.roundID), new anchor.BN(params.marketValue), {fixed: {}}, null)
                         .accounts({governanceAuthority: dev.publicKey})
                         .signers([dev])
                         .rpc();
@@ -872,7 +914,7 @@ describe("sol-xr", async () => {
                     [Buffer.from("mint_round"), idBuffer],
                     program.programId
                 );
-                await program.methods.buySolxr(new anchor.BN(params.roundID), new anchor.BN(params.amount))
+                await program.methods.buySolxr(new anchor.BN(params.roundID), new anchor.BN(params.amount), new anchor.BN(0), null)
                     .accounts({investor: investor.publicKey, platformAddress: platformDesignatedAccount.publicKey})
                     .signers([investor])
                     .rpc();
@@ -887,7 +929,7 @@ describe("sol-xr", async () => {
                             [Buffer.from("mint_round"), idBuffer],
                             program.programId
                         );
-                        await program.methods.buySolxr(new anchor.BN(params.roundID), new anchor.BN(params.amount))
+                        await program.methods.buySolxr(new anchor.BN(params.roundID), new anchor.BN(params.amount), new anchor.BN(0), null)
                             .accounts({
                                 investor: investor.publicKey,
                                 platformAddress: platformDesignatedAccount.publicKey
@@ -910,7 +952,7 @@ describe("sol-xr", async () => {
                             ),
                         );
                     }
-                    await program.methods.buySolxr(new anchor.BN(params.roundID), new anchor.BN(params.amount))
+                    await program.methods.buySolxr(new anchor.BN(params.roundID), new anchor.BN(params.amount), new anchor.BN(0), null)
                         .accounts({investor: investor.publicKey, platformAddress: platformDesignatedAccount.publicKey})
                         .signers([investor])
                         .rpc();
@@ -956,7 +998,7 @@ describe("sol-xr", async () => {
             try {
                 let investor = Keypair.generate()
                 await fundAccount(investor, 500)
-                await program.methods.buySolxr(new anchor.BN(roundID), new anchor.BN(maxMintPerWallet))
+                await program.methods.buySolxr(new anchor.BN(roundID), new anchor.BN(maxMintPerWallet), new anchor.BN(0), null)
                     .accounts({investor: investor.publicKey, platformAddress: platformDesignatedAccount.publicKey})
                     .signers([investor])
                     .rpc();
@@ -981,7 +1023,7 @@ describe("sol-xr", async () => {
             const roundID = 1
             let lateInvestor = Keypair.generate()
             await fundAccount(lateInvestor, 500)
-            await program.methods.buySolxr(new anchor.BN(roundID), new anchor.BN(maxMintPerWallet))
+            await program.methods.buySolxr(new anchor.BN(roundID), new anchor.BN(maxMintPerWallet), new anchor.BN(0), null)
                 .accounts({investor: lateInvestor.publicKey, platformAddress: platformDesignatedAccount.publicKey})
                 .signers([lateInvestor])
                 .rpc();
@@ -1043,7 +1085,7 @@ describe("sol-xr", async () => {
             let lateInvestor = Keypair.generate()
             await fundAccount(lateInvestor, 500)
 
-            await program.methods.buySolxr(new anchor.BN(1), new anchor.BN(maxMintPerWallet))
+            await program.methods.buySolxr(new anchor.BN(1), new anchor.BN(maxMintPerWallet), new anchor.BN(0), null)
                 .accounts({investor: lateInvestor.publicKey, platformAddress: platformDesignatedAccount.publicKey})
                 .signers([lateInvestor])
                 .rpc();
@@ -1058,7 +1100,7 @@ describe("sol-xr", async () => {
 
     await it('should fail address trying to invest in old round', async () => {
         try {
-            await program.methods.openMintRound(new anchor.BN(2), new anchor.BN(2 * LAMPORTS_PER_SOL))
+            await program.methods.openMintRound(new anchor.BN(2), new anchor.BN(2 * LAMPORTS_PER_SOL), {fixed: {}}, null)
                 .accounts({governanceAuthority: dev.publicKey})
                 .signers([dev])
                 .rpc();
@@ -1066,7 +1108,7 @@ describe("sol-xr", async () => {
             let lateInvestor = Keypair.generate()
             await fundAccount(lateInvestor, 500)
 
-            await program.methods.buySolxr(new anchor.BN(1), new anchor.BN(maxMintPerWallet))
+            await program.methods.buySolxr(new anchor.BN(1), new anchor.BN(maxMintPerWallet), new anchor.BN(0), null)
                 .accounts({investor: lateInvestor.publicKey, platformAddress: platformDesignatedAccount.publicKey})
                 .signers([lateInvestor])
                 .rpc();
@@ -1108,7 +1150,14 @@ describe("sol-xr", async () => {
                 price,
                 maxMintPerWallet,
                 startTime,
-                endTime
+                endTime,
+                new anchor.BN(0),
+                new anchor.BN(0),
+                new anchor.BN(0),
+                maturity.add(new anchor.BN(7200)),
+                null,
+                null,
+                null,
             )
                 .accounts({governanceAuthority: badActor.publicKey})
                 .signers([badActor])
@@ -1144,7 +1193,14 @@ describe("sol-xr", async () => {
             price,
             maxMintPerWallet,
             startTime,
-            endTime
+            endTime,
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            maturity.add(new anchor.BN(7200)),
+            null,
+            null,
+            null,
         )
             .accounts({governanceAuthority: dev.publicKey})
             .signers([dev])
@@ -1319,7 +1375,7 @@ describe("sol-xr", async () => {
                     );
                     tx.add(
                         await program.methods
-                            .buyBond(new anchor.BN(solStrategy.nextBondId.toNumber() - 1))
+                            .buyBond(new anchor.BN(solStrategy.nextBondId.toNumber() - 1), new anchor.BN(1), null)
                             .accounts({buyer: buyer.publicKey})
                             .instruction()
                     );
@@ -1357,7 +1413,7 @@ describe("sol-xr", async () => {
                     expect(edition.parent).equal(bondEditionPDA.toBase58(), `parent of mint edition should be ${bondNFTPDA.toBase58()}`)
                 } else {
                     try {
-                        await program.methods.buyBond(new anchor.BN(solStrategy.nextBondId.toNumber() - 1))
+                        await program.methods.buyBond(new anchor.BN(solStrategy.nextBondId.toNumber() - 1), new anchor.BN(1), null)
                             .accounts({buyer: buyer.publicKey})
                             .signers([buyer])
                             .rpc();
@@ -1394,6 +1450,9 @@ describe("sol-xr", async () => {
 
         await transferTokens(secondBuyer, lateBuyer.publicKey, buyerBondNFTPDA, 1)
 
+        // rent of the edition record created when buying
+        const bondEditionRecordRent = (program.account.bondEditionRecord.size + 128) * 6960;
+
         const testCases = [
             {
                 desc: "when account doesn't own edition",
@@ -1430,7 +1489,7 @@ describe("sol-xr", async () => {
                 expectedValue: {
                     solInTreasury: solStrategy.solInTreasury.toNumber(),
                     solFromBond: solStrategy.solFromBond.toNumber() - bond.price.toNumber(),
-                    newBuyerBalance: 4999975599760,
+                    newBuyerBalance: 4999975599760 - bondEditionRecordRent,
                 },
                 shouldSucceed: true,
                 time: bond.maturity.toNumber(),
@@ -1446,7 +1505,7 @@ describe("sol-xr", async () => {
                 expectedValue: {
                     solInTreasury: solStrategy.solInTreasury.toNumber(),
                     solFromBond: solStrategy.solFromBond.toNumber() - bond.price.toNumber(),
-                    newBuyerBalance: 4999975599760,
+                    newBuyerBalance: 4999975599760 - bondEditionRecordRent,
                 },
                 shouldSucceed: false,
                 time: bond.maturity.toNumber(),
@@ -1462,7 +1521,7 @@ describe("sol-xr", async () => {
                 expectedValue: {
                     solFromBond: solStrategy.solFromBond.toNumber() - (bond.price.toNumber() * 2),
                     solInTreasury: solStrategy.solInTreasury.toNumber() + bond.price.toNumber(),
-                    newBuyerBalance: 4989976713360,
+                    newBuyerBalance: 4989976713360 - bondEditionRecordRent,
                     newSolxrBalance: 6666666666,
                 },
                 shouldSucceed: false,
@@ -1518,16 +1577,27 @@ describe("sol-xr", async () => {
                 );
             }
             if (shouldSucceed) {
-                await program.methods.convertBond(
-                    new anchor.BN(solStrategy.nextBondId.toNumber() - 1),
-                    new anchor.BN(params.edition),
-                    params.convert
-                )
-                    .accounts({buyer: buyer.publicKey})
-                    .signers([buyer])
-                    .rpc();
+                const events = await rpcWithEvents(
+                    program.methods.convertBond(
+                        new anchor.BN(solStrategy.nextBondId.toNumber() - 1),
+                        new anchor.BN(params.edition),
+                        params.convert,
+                        new anchor.BN(0),
+                    )
+                        .accounts({buyer: buyer.publicKey}),
+                    [buyer]
+                );
 
                 const newSolStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+                if (params.convert) {
+                    const converted = findEvent(events, "BondConverted");
+                    expect(converted.holder.toBase58()).equal(buyer.publicKey.toBase58())
+                    expect(converted.bondId.toNumber()).equal(solStrategy.nextBondId.toNumber() - 1)
+                    expect(converted.editionNumber.toNumber()).equal(Number(params.edition))
+                    expect(converted.solAmount.toNumber()).equal(bond.price.toNumber())
+                    expect(converted.strikePrice.toNumber()).equal(bond.strikePrice.toNumber())
+                    expect(converted.solxrMinted.toNumber()).equal(expectedValue.newSolxrBalance)
+                }
 
                 const editionBuffer = Buffer.alloc(8);
                 editionBuffer.writeBigUInt64LE(BigInt(params.edition));
@@ -1551,7 +1621,8 @@ describe("sol-xr", async () => {
                     await program.methods.convertBond(
                         new anchor.BN(solStrategy.nextBondId.toNumber() - 1),
                         new anchor.BN(params.edition),
-                        params.convert
+                        params.convert,
+                        new anchor.BN(0),
                     )
                         .accounts({buyer: buyer.publicKey})
                         .signers([buyer])
@@ -1569,90 +1640,2532 @@ describe("sol-xr", async () => {
 
     })
 
-    async function getMasterEdition(mintAddress: PublicKey): Promise<MasterEdition> {
-        // Get master edition PDA
-        const [editionPDA] = PublicKey.findProgramAddressSync(
-            [
-                Buffer.from('metadata'),
-                METADATA_PROGRAM_ID.toBuffer(),
-                mintAddress.toBuffer(),
-                Buffer.from('edition')
-            ],
-            METADATA_PROGRAM_ID
-        );
-        // Fetch the accounts
-        const editionAccountInfo = await provider.connection.getAccountInfo(editionPDA);
+    /// Governance
+    await it("should fail to update strategy params", async () => {
+        const badActor = Keypair.generate();
+        await fundAccount(badActor, 5000)
 
+        const testCases = [
+            {
+                desc: "caller is not governance",
+                signer: badActor,
+                params: {platformMintFee: new anchor.BN(10_000_000), mintingRounds: null},
+                errorCode: "UnauthorizedGovernanceAuthority",
+            },
+            {
+                desc: "platform mint fee is above ceiling",
+                signer: dev,
+                params: {platformMintFee: new anchor.BN(100_000_001), mintingRounds: null},
+                errorCode: "PlatformMintFeeTooHigh",
+            },
+            {
+                desc: "minting rounds is below next minting round",
+                signer: dev,
+                params: {platformMintFee: null, mintingRounds: new anchor.BN(1)},
+                errorCode: "MintingRoundsBelowNextRound",
+            },
+        ]
+        for (const {desc, signer, params, errorCode} of testCases) {
+            console.log(`when ${desc}`)
+            try {
+                await queueGovernanceAction(strategyParams(params), signer);
+                expect.fail("Expected an error but the instruction succeeded");
+            } catch (error: any) {
+                let msg = error.message as string
+                console.log(msg, "->", errorCode)
+                expect(msg.includes('AnchorError')).true
+                expect(msg.includes(`Error Code: ${errorCode}`)).true
+            }
+        }
+    })
 
-        return deserializeMasterEdition({
-            executable: editionAccountInfo.executable,
-            rentEpoch: BigInt(editionAccountInfo.rentEpoch),
-            lamports: lamports(editionAccountInfo.lamports),
-            owner: editionAccountInfo.owner.toBase58() as MTPublicKey,
-            data: editionAccountInfo.data,
-            publicKey: editionPDA.toBase58() as MTPublicKey
-        })
-    }
+    await it("should update strategy params", async () => {
+        const before = await program.account.solStrategy.fetch(solStrategyPDA)
+        const platformMintFee = new anchor.BN(20_000_000);
+        const mintDuration = new anchor.BN(43_200);
 
-    async function getEdition(mintAddress: PublicKey): Promise<Edition> {
-        // Get master edition PDA
-        const [editionPDA] = PublicKey.findProgramAddressSync(
-            [
-                Buffer.from('metadata'),
-                METADATA_PROGRAM_ID.toBuffer(),
-                mintAddress.toBuffer(),
-                Buffer.from('edition')
-            ],
-            METADATA_PROGRAM_ID
-        );
-        // Fetch the accounts
-        const editionAccountInfo = await provider.connection.getAccountInfo(editionPDA);
+        await executeGovernanceAction(strategyParams({platformMintFee, mintDuration}));
 
-        return deserializeEdition({
-            executable: editionAccountInfo.executable,
-            rentEpoch: BigInt(editionAccountInfo.rentEpoch),
-            lamports: lamports(editionAccountInfo.lamports),
-            owner: editionAccountInfo.owner.toBase58() as MTPublicKey,
-            data: editionAccountInfo.data,
-            publicKey: editionPDA.toBase58() as MTPublicKey
-        })
-    }
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.platformMintFee.toNumber()).equal(platformMintFee.toNumber(), "platform mint fee should be updated")
+        expect(solStrategy.mintDuration.toNumber()).equal(mintDuration.toNumber(), "mint duration should be updated")
+        expect(solStrategy.maxPlatformMintFee.toNumber()).equal(before.maxPlatformMintFee.toNumber(), "max platform mint fee should be unchanged")
+        expect(solStrategy.mintingRounds.toNumber()).equal(before.mintingRounds.toNumber(), "minting rounds should be unchanged")
+    })
 
-    async function transferTokens(
-        sender: Keypair,
-        receiver: PublicKey,
-        mint: PublicKey,
-        amount: number
-    ): Promise<void> {
-        const sourceATA = await getAssociatedTokenAddress(mint, sender.publicKey);
+    await it("should transfer governance authority in two steps", async () => {
+        const newGovernance = Keypair.generate();
+        await fundAccount(newGovernance, 10)
+        const badActor = Keypair.generate();
+        await fundAccount(badActor, 10)
 
-        const destinationATA = await getAssociatedTokenAddress(mint, receiver);
+        await expectError(
+            queueGovernanceAction({proposeGovernanceAuthority: {newGovernanceAuthority: newGovernance.publicKey}}, badActor),
+            "UnauthorizedGovernanceAuthority"
+        )
+        await expectError(
+            program.methods.cancelGovernanceAuthority()
+                .accounts({governanceAuthority: dev.publicKey})
+                .signers([dev])
+                .rpc(),
+            "NoPendingGovernanceAuthority"
+        )
 
-        const createATAIx = createAssociatedTokenAccountInstruction(
-            sender.publicKey,
-            destinationATA,
-            receiver,
-            mint
+        await executeGovernanceAction({proposeGovernanceAuthority: {newGovernanceAuthority: badActor.publicKey}});
+        await program.methods.cancelGovernanceAuthority()
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.pendingGovernanceAuthority).equal(null, "pending governance authority should be cleared")
+
+        await executeGovernanceAction({proposeGovernanceAuthority: {newGovernanceAuthority: newGovernance.publicKey}});
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.governanceAuthority.toBase58()).equal(dev.publicKey.toBase58(), "governance authority should not change before acceptance")
+        expect(solStrategy.pendingGovernanceAuthority.toBase58()).equal(newGovernance.publicKey.toBase58())
+
+        await expectError(
+            program.methods.acceptGovernanceAuthority()
+                .accounts({pendingGovernanceAuthority: badActor.publicKey})
+                .signers([badActor])
+                .rpc(),
+            "UnauthorizedPendingGovernanceAuthority"
+        )
+
+        await program.methods.acceptGovernanceAuthority()
+            .accounts({pendingGovernanceAuthority: newGovernance.publicKey})
+            .signers([newGovernance])
+            .rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.governanceAuthority.toBase58()).equal(newGovernance.publicKey.toBase58(), "governance authority should be rotated")
+        expect(solStrategy.pendingGovernanceAuthority).equal(null)
+
+        // hand governance back so the remaining tests keep using the dev key
+        await executeGovernanceAction({proposeGovernanceAuthority: {newGovernanceAuthority: dev.publicKey}}, newGovernance);
+        await program.methods.acceptGovernanceAuthority()
+            .accounts({pendingGovernanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+    })
+
+    await it("should update platform fee recipients", async () => {
+        const newPlatform = Keypair.generate();
+        const feeVault = Keypair.generate();
+        const badActor = Keypair.generate();
+        await fundAccount(badActor, 10)
+
+        await expectError(
+            queueGovernanceAction({updatePlatformAddress: {platformAddress: newPlatform.publicKey}}, badActor),
+            "UnauthorizedGovernanceAuthority"
+        )
+        await expectError(
+            queueGovernanceAction({
+                updatePlatformFeeSplits: {
+                    platformFeeSplits: [
+                        {recipient: feeVault.publicKey, basisPoints: 6_000},
+                        {recipient: newPlatform.publicKey, basisPoints: 4_001},
+                    ]
+                }
+            }),
+            "FeeSplitsExceedTotal"
+        )
+        await expectError(
+            queueGovernanceAction({
+                updatePlatformFeeSplits: {
+                    platformFeeSplits: [
+                        {recipient: feeVault.publicKey, basisPoints: 1_000},
+                        {recipient: feeVault.publicKey, basisPoints: 1_000},
+                    ]
+                }
+            }),
+            "DuplicateFeeRecipient"
+        )
+
+        await executeGovernanceAction({updatePlatformAddress: {platformAddress: newPlatform.publicKey}});
+        await executeGovernanceAction({
+            updatePlatformFeeSplits: {platformFeeSplits: [{recipient: feeVault.publicKey, basisPoints: 2_500}]}
+        });
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.platformAddress.toBase58()).equal(newPlatform.publicKey.toBase58(), "platform address should be updated")
+        expect(solStrategy.platformFeeSplits.length).equal(1)
+        expect(solStrategy.platformFeeSplits[0].recipient.toBase58()).equal(feeVault.publicKey.toBase58())
+        expect(solStrategy.platformFeeSplits[0].basisPoints).equal(2_500)
+
+        // restore the original fee destination
+        await executeGovernanceAction({updatePlatformAddress: {platformAddress: platformDesignatedAccount.publicKey}});
+        await executeGovernanceAction({updatePlatformFeeSplits: {platformFeeSplits: []}});
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.platformFeeSplits.length).equal(0)
+    })
+
+    /// Redemption
+    await it("should redeem solxr for treasury sol", async () => {
+        const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+        await expectError(
+            program.methods.redeemSolxr(amount, new anchor.BN(0))
+                .accounts({redeemer: firstWBuyer.publicKey})
+                .signers([firstWBuyer])
+                .rpc(),
+            "RedemptionNotAllowed"
+        )
+
+        await executeGovernanceAction(redemptionParams(true, null, null));
+
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solxrBefore = await getMint(provider.connection, tokenPDA)
+        const buyerBefore = await provider.connection.getAccountInfo(firstWBuyer.publicKey);
+
+        const solValue = BigInt(amount.toString()) * BigInt(solStrategy.solInTreasury.toString()) / solxrBefore.supply;
+        const fee = solValue * BigInt(solStrategy.redemptionFee.toString()) / BigInt(LAMPORTS_PER_SOL);
+        const solToReturn = solValue - fee;
+
+        await expectError(
+            program.methods.redeemSolxr(amount, new anchor.BN((solToReturn + BigInt(1)).toString()))
+                .accounts({redeemer: firstWBuyer.publicKey})
+                .signers([firstWBuyer])
+                .rpc(),
+            "SlippageExceeded"
+        )
+        await program.methods.redeemSolxr(amount, new anchor.BN(solToReturn.toString()))
+            .accounts({redeemer: firstWBuyer.publicKey})
+            .signers([firstWBuyer])
+            .rpc();
+
+        const newSolStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solxrAfter = await getMint(provider.connection, tokenPDA)
+        const buyerAfter = await provider.connection.getAccountInfo(firstWBuyer.publicKey);
+
+        expect(solxrAfter.supply).equal(solxrBefore.supply - BigInt(amount.toString()), "redeemed solxr should be burned")
+        expect(BigInt(newSolStrategy.solInTreasury.toString())).equal(BigInt(solStrategy.solInTreasury.toString()) - solToReturn, "sol in treasury should drop by the sol returned")
+        expect(BigInt(newSolStrategy.redeemedInEpoch.toString())).equal(solToReturn)
+        expect(buyerAfter.lamports > buyerBefore.lamports).true
+
+        // cap the epoch at what has already been redeemed
+        await executeGovernanceAction(redemptionParams(null, null, newSolStrategy.redeemedInEpoch));
+        await expectError(
+            program.methods.redeemSolxr(amount, new anchor.BN(0))
+                .accounts({redeemer: firstWBuyer.publicKey})
+                .signers([firstWBuyer])
+                .rpc(),
+            "ExceedsRedemptionCap"
+        )
+
+        await executeGovernanceAction(redemptionParams(false, null, null));
+    })
+
+    /// Market value oracle
+    await it("should open round for minting with oracle market value", async () => {
+        const priceOracle = Keypair.generate().publicKey;
+
+        const oracleConfig = (priceOracle: PublicKey | null, maxStaleness: number, maxConfidence: number, maxDeviation: number) => ({
+            configureOracle: {
+                priceOracle,
+                maxStaleness: new anchor.BN(maxStaleness),
+                maxConfidence: new anchor.BN(maxConfidence),
+                maxDeviation: new anchor.BN(maxDeviation),
+            }
+        });
+        await expectError(
+            queueGovernanceAction(oracleConfig(priceOracle, 0, 10_000_000, 50_000_000)),
+            "InvalidMaxStaleness"
+        )
+        await executeGovernanceAction(oracleConfig(priceOracle, 60, 10_000_000, 50_000_000));
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solxr = await getMint(provider.connection, tokenPDA)
+        const nav = BigInt(solStrategy.solInTreasury.toString()) * BigInt(LAMPORTS_PER_SOL) / solxr.supply;
+        const oraclePrice = nav * BigInt(3);
+        const roundID = new anchor.BN(solStrategy.nextMintingRounds.toNumber());
+        const currentClock = await provider.context.banksClient.getClock();
+
+        await expectError(
+            program.methods.openMintRound(roundID, new anchor.BN(oraclePrice.toString()), {fixed: {}}, null)
+                .accounts({governanceAuthority: dev.publicKey, priceOracle: null})
+                .signers([dev])
+                .rpc(),
+            "PriceOracleRequired"
+        )
+
+        setPriceAccount(priceOracle, oraclePrice, oraclePrice / BigInt(1000), currentClock.unixTimestamp - BigInt(61))
+        await expectError(
+            program.methods.openMintRound(roundID, new anchor.BN(oraclePrice.toString()), {fixed: {}}, null)
+                .accounts({governanceAuthority: dev.publicKey, priceOracle})
+                .signers([dev])
+                .rpc(),
+            "StalePrice"
+        )
+
+        setPriceAccount(priceOracle, oraclePrice, oraclePrice / BigInt(1000), currentClock.unixTimestamp)
+        await expectError(
+            program.methods.openMintRound(roundID, new anchor.BN((oraclePrice * BigInt(2)).toString()), {fixed: {}}, null)
+                .accounts({governanceAuthority: dev.publicKey, priceOracle})
+                .signers([dev])
+                .rpc(),
+            "MarketValueDeviatesFromOracle"
+        )
+
+        await program.methods.openMintRound(roundID, new anchor.BN(0), {fixed: {}}, null)
+            .accounts({governanceAuthority: dev.publicKey, priceOracle})
+            .signers([dev])
+            .rpc();
+
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(roundID.toNumber()));
+        const [mintRoundPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("mint_round"), idBuffer],
+            program.programId
         );
+        const mintRound = await program.account.mintRound.fetch(mintRoundPDA)
+        expect(mintRound.premium.toString()).equal(oraclePrice.toString(), "premium should be the oracle price")
 
-        const transferIx = createTransferInstruction(
-            sourceATA,
-            destinationATA,
-            sender.publicKey,
-            amount
+        await closeMintingRound(dev)
+        await executeGovernanceAction(oracleConfig(null, 0, 0, 0));
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.priceOracle).equal(null)
+    })
+
+    /// Market value TWAP
+    await it("should open round for minting with TWAP market value", async () => {
+        const reporter = Keypair.generate();
+        await fundAccount(reporter, 1)
+
+        const twapConfig = (twapWindow: number, minObservationInterval: number, priceReporter: PublicKey | null) => ({
+            configureTwap: {
+                twapWindow: new anchor.BN(twapWindow),
+                minObservationInterval: new anchor.BN(minObservationInterval),
+                priceReporter,
+                poolSolxrVault: null,
+                poolSolVault: null,
+            }
+        });
+        await expectError(
+            queueGovernanceAction(twapConfig(600, 10, reporter.publicKey)),
+            "WindowExceedsObservationCapacity"
+        )
+        const {id} = await queueGovernanceAction(twapConfig(600, 60, reporter.publicKey));
+        await expectError(
+            program.methods.executeGovernanceAction(id)
+                .accounts({proposer: dev.publicKey})
+                .rpc(),
+            "GovernanceActionNotReady"
+        )
+        await program.methods.cancelGovernanceAction(id)
+            .accounts({governanceAuthority: dev.publicKey, proposer: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        await executeGovernanceAction(twapConfig(600, 60, reporter.publicKey));
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.twapWindow.toNumber()).equal(600)
+        const solxr = await getMint(provider.connection, tokenPDA)
+        const nav = BigInt(solStrategy.solInTreasury.toString()) * BigInt(LAMPORTS_PER_SOL) / solxr.supply;
+        const price = nav * BigInt(3);
+        const roundID = new anchor.BN(solStrategy.nextMintingRounds.toNumber());
+
+        await expectError(
+            program.methods.recordPriceObservation(new anchor.BN(price.toString()))
+                .accounts({reporter: dev.publicKey, poolSolxrVault: null, poolSolVault: null})
+                .signers([dev])
+                .rpc(),
+            "UnauthorizedPriceReporter"
+        )
+        await program.methods.recordPriceObservation(new anchor.BN(price.toString()))
+            .accounts({reporter: reporter.publicKey, poolSolxrVault: null, poolSolVault: null})
+            .signers([reporter])
+            .rpc();
+        await expectError(
+            program.methods.recordPriceObservation(new anchor.BN((price + BigInt(1)).toString()))
+                .accounts({reporter: reporter.publicKey, poolSolxrVault: null, poolSolVault: null})
+                .signers([reporter])
+                .rpc(),
+            "ObservationTooEarly"
+        )
+
+        let currentClock = await provider.context.banksClient.getClock();
+        const firstObservation = currentClock.unixTimestamp;
+        provider.context.setClock(
+            new Clock(
+                currentClock.slot,
+                currentClock.epochStartTimestamp,
+                currentClock.epoch,
+                currentClock.leaderScheduleEpoch,
+                firstObservation + BigInt(300),
+            ),
         );
+        await program.methods.recordPriceObservation(new anchor.BN((price + BigInt(2)).toString()))
+            .accounts({reporter: reporter.publicKey, poolSolxrVault: null, poolSolVault: null})
+            .signers([reporter])
+            .rpc();
 
-        const tx = new Transaction().add(createATAIx).add(transferIx);
-        await provider.sendAndConfirm(tx, [sender]);
-    }
+        await expectError(
+            program.methods.openMintRound(roundID, new anchor.BN(price.toString()), {fixed: {}}, null)
+                .accounts({governanceAuthority: dev.publicKey, priceOracle: null, priceObservation: null})
+                .signers([dev])
+                .rpc(),
+            "PriceObservationRequired"
+        )
+        await expectError(
+            program.methods.openMintRound(roundID, new anchor.BN(price.toString()), {fixed: {}}, null)
+                .accounts({governanceAuthority: dev.publicKey, priceOracle: null, priceObservation: priceObservationPDA})
+                .signers([dev])
+                .rpc(),
+            "InsufficientPriceObservations"
+        )
 
-    async function fundAccount(keyPair: Keypair, amount: number) {
-        const instruction = SystemProgram.transfer({
-            fromPubkey: providerKeypair.publicKey,
-            toPubkey: keyPair.publicKey,
-            lamports: amount * LAMPORTS_PER_SOL,
+        currentClock = await provider.context.banksClient.getClock();
+        provider.context.setClock(
+            new Clock(
+                currentClock.slot,
+                currentClock.epochStartTimestamp,
+                currentClock.epoch,
+                currentClock.leaderScheduleEpoch,
+                firstObservation + BigInt(601),
+            ),
+        );
+        await program.methods.openMintRound(roundID, new anchor.BN(price.toString()), {fixed: {}}, null)
+            .accounts({governanceAuthority: dev.publicKey, priceOracle: null, priceObservation: priceObservationPDA})
+            .signers([dev])
+            .rpc();
+
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.allowNewMint).equal(true)
+
+        await closeMintingRound(dev)
+        await executeGovernanceAction(twapConfig(0, 0, null));
+    })
+
+    /// TWAP manipulation
+    await it("should bound the TWAP move of a pool price manipulated for a single observation", async () => {
+        const {unixTimestamp} = await provider.context.banksClient.getClock();
+        const poolSolxrVault = Keypair.generate().publicKey;
+        const poolSolVault = Keypair.generate().publicKey;
+        const setPoolReserves = (solxrReserve: bigint, solReserve: bigint) => {
+            for (const [address, mint, amount] of [[poolSolxrVault, tokenPDA, solxrReserve], [poolSolVault, NATIVE_MINT, solReserve]] as const) {
+                const data = Buffer.alloc(ACCOUNT_SIZE);
+                AccountLayout.encode({
+                    mint,
+                    owner: dev.publicKey,
+                    amount,
+                    delegateOption: 0,
+                    delegate: PublicKey.default,
+                    state: 1,
+                    isNativeOption: 0,
+                    isNative: BigInt(0),
+                    delegatedAmount: BigInt(0),
+                    closeAuthorityOption: 0,
+                    closeAuthority: PublicKey.default,
+                }, data);
+                provider.context.setAccount(address, {
+                    lamports: LAMPORTS_PER_SOL,
+                    data,
+                    owner: TOKEN_PROGRAM_ID,
+                    executable: false,
+                });
+            }
+        };
+        const crank = async (timestamp: bigint) => {
+            await setUnixTimestamp(timestamp);
+            const events = await rpcWithEvents(
+                program.methods.recordPriceObservation(null)
+                    .accounts({reporter: dev.publicKey, poolSolxrVault, poolSolVault}),
+                [dev]
+            );
+            return BigInt(findEvent(events, "PriceObserved").price.toString());
+        };
+        const twapConfig = (twapWindow: number, minObservationInterval: number, withPool: boolean) => ({
+            configureTwap: {
+                twapWindow: new anchor.BN(twapWindow),
+                minObservationInterval: new anchor.BN(minObservationInterval),
+                priceReporter: null,
+                poolSolxrVault: withPool ? poolSolxrVault : null,
+                poolSolVault: withPool ? poolSolVault : null,
+            }
         });
-        const transaction = new Transaction().add(instruction);
-        await provider.sendAndConfirm(transaction, [providerKeypair.payer]);
+        await executeGovernanceAction(twapConfig(600, 60, true));
+        let priceObservation = await program.account.priceObservation.fetch(priceObservationPDA);
+        expect(priceObservation.count).equal(0, "configuring the TWAP should drop the observations of the previous source");
+
+        const fairPrice = BigInt(2 * LAMPORTS_PER_SOL);
+        setPoolReserves(BigInt(1_000 * LAMPORTS_PER_SOL), BigInt(2_000 * LAMPORTS_PER_SOL));
+        for (let i = 0; i < 6; i++) {
+            expect(await crank(unixTimestamp + BigInt(60 * i))).equal(fairPrice);
+        }
+
+        // a swap pushes the spot price tenfold, the crank is sent and the swap is reversed
+        setPoolReserves(BigInt(100 * LAMPORTS_PER_SOL), BigInt(2_000 * LAMPORTS_PER_SOL));
+        const manipulatedPrice = await crank(unixTimestamp + BigInt(360));
+        expect(manipulatedPrice).equal(fairPrice * BigInt(105) / BigInt(100), "the observation should be clamped to 5% from the fair price");
+        setPoolReserves(BigInt(1_000 * LAMPORTS_PER_SOL), BigInt(2_000 * LAMPORTS_PER_SOL));
+        for (let i = 7; i < 12; i++) {
+            expect(await crank(unixTimestamp + BigInt(60 * i))).equal(fairPrice);
+        }
+
+        // the clamped price only weighs on the minute until the next observation
+        priceObservation = await program.account.priceObservation.fetch(priceObservationPDA);
+        const latest = priceObservation.observations[(priceObservation.nextIndex + 31) % 32];
+        const windowStart = priceObservation.observations[(priceObservation.nextIndex + 31 - 10) % 32];
+        const twap = (BigInt(latest.cumulativePrice.toString()) - BigInt(windowStart.cumulativePrice.toString()))
+            / (BigInt(latest.timestamp.toString()) - BigInt(windowStart.timestamp.toString()));
+        expect(twap).equal(fairPrice + (manipulatedPrice - fairPrice) / BigInt(10));
+
+        await setUnixTimestamp(unixTimestamp);
+        await executeGovernanceAction(twapConfig(0, 0, false));
+    })
+
+    /// Pause
+    await it("should pause and unpause user-facing instructions", async () => {
+        const pauseRedemptions = 1 << 5;
+        const amount = new anchor.BN(LAMPORTS_PER_SOL / 10);
+
+        await expectError(
+            program.methods.setPause(pauseRedemptions)
+                .accounts({governanceAuthority: firstWBuyer.publicKey})
+                .signers([firstWBuyer])
+                .rpc(),
+            "UnauthorizedGovernanceAuthority"
+        )
+        await expectError(
+            program.methods.setPause(1 << 6)
+                .accounts({governanceAuthority: dev.publicKey})
+                .signers([dev])
+                .rpc(),
+            "InvalidPauseFlags"
+        )
+
+        await program.methods.setPause(pauseRedemptions)
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        await executeGovernanceAction(redemptionParams(true, null, new anchor.BN(100 * LAMPORTS_PER_SOL)));
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).equal(pauseRedemptions)
+        await expectError(
+            program.methods.redeemSolxr(amount, new anchor.BN(0))
+                .accounts({redeemer: firstWBuyer.publicKey})
+                .signers([firstWBuyer])
+                .rpc(),
+            "ProtocolPaused"
+        )
+
+        await program.methods.setPause(0)
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        await program.methods.redeemSolxr(amount, new anchor.BN(0))
+            .accounts({redeemer: firstWBuyer.publicKey})
+            .signers([firstWBuyer])
+            .rpc();
+
+        await executeGovernanceAction(redemptionParams(false, null, null));
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).equal(0)
+    })
+
+    /// Guardian
+    await it("should let the guardian pause but not unpause", async () => {
+        const guardian = Keypair.generate();
+        await fundAccount(guardian, 1)
+        const pauseBonds = 1 << 2;
+
+        await expectError(
+            program.methods.pause(pauseBonds)
+                .accounts({authority: guardian.publicKey})
+                .signers([guardian])
+                .rpc(),
+            "UnauthorizedPauseAuthority"
+        )
+
+        await expectError(
+            queueGovernanceAction({setGuardian: {guardian: PublicKey.default}}),
+            "InvalidGuardian"
+        )
+        await executeGovernanceAction({setGuardian: {guardian: guardian.publicKey}});
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.guardian.toBase58()).equal(guardian.publicKey.toBase58())
+
+        await program.methods.pause(pauseBonds)
+            .accounts({authority: guardian.publicKey})
+            .signers([guardian])
+            .rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).equal(pauseBonds)
+
+        await expectError(
+            program.methods.setPause(0)
+                .accounts({governanceAuthority: guardian.publicKey})
+                .signers([guardian])
+                .rpc(),
+            "UnauthorizedGovernanceAuthority"
+        )
+        await expectError(
+            queueGovernanceAction({setGuardian: {guardian: null}}, guardian),
+            "UnauthorizedGovernanceAuthority"
+        )
+
+        await program.methods.setPause(0)
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        await executeGovernanceAction({setGuardian: {guardian: null}});
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).equal(0)
+        expect(solStrategy.guardian).equal(null)
+    })
+
+    /// Batch purchase
+    await it("should buy several whitelist editions in one call", async () => {
+        const buyer = Keypair.generate();
+        await fundAccount(buyer, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const maxMintPerWallet = new anchor.BN(3);
+        const price = new anchor.BN(LAMPORTS_PER_SOL);
+
+        await program.methods.sellWhitelist(
+            "Whitelist Batch",
+            "WLB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            price,
+            new anchor.BN(now + 3600),
+            new anchor.BN(now + 7200),
+            new anchor.BN(1_000),
+            maxMintPerWallet,
+            new anchor.BN(now),
+            new anchor.BN(now + 600),
+            false,
+            null,
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const whitelistId = new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1);
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(whitelistId.toNumber()));
+        const [whitelistPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("whitelist"), idBuffer],
+            program.programId
+        );
+        const [whitelistNFTPDA] = PublicKey.findProgramAddressSync(
+            [whitelistPDA.toBuffer()],
+            program.programId
+        );
+        const solInTreasuryBefore = solStrategy.solInTreasury.toNumber();
+
+        const buyTx = async (quantity: number, firstEdition: number) => {
+            const printAccounts = [];
+            const recordAccounts = [];
+            for (let edition = firstEdition + 1; edition < firstEdition + quantity; edition++) {
+                printAccounts.push(...await printEditionAccounts(whitelistPDA, whitelistNFTPDA, buyer.publicKey, edition));
+                recordAccounts.push({pubkey: whitelistEditionRecord(whitelistPDA, edition), isSigner: false, isWritable: true});
+            }
+            const remainingAccounts = [...printAccounts, ...recordAccounts];
+            const tx = new anchor.web3.Transaction();
+            tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 600_000}));
+            tx.add(
+                await program.methods
+                    .buyWhitelist(whitelistId, new anchor.BN(quantity), null)
+                    .accounts({buyer: buyer.publicKey})
+                    .remainingAccounts(remainingAccounts)
+                    .instruction()
+            );
+            return provider.sendAndConfirm(tx, [buyer]);
+        }
+
+        // a pre-funded edition mint address must not block the purchase
+        const [secondEditionMint] = await printEditionAccounts(whitelistPDA, whitelistNFTPDA, buyer.publicKey, 2);
+        await provider.sendAndConfirm(
+            new Transaction().add(SystemProgram.transfer({fromPubkey: dev.publicKey, toPubkey: secondEditionMint.pubkey, lamports: LAMPORTS_PER_SOL / 100})),
+            [dev]
+        );
+        await buyTx(2, 1);
+
+        const whitelist = await program.account.whitelist.fetch(whitelistPDA)
+        const [whitelistRecordPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("whitelist_record"), whitelistPDA.toBuffer(), buyer.publicKey.toBuffer()],
+            program.programId
+        );
+        const whitelistRecord = await program.account.whitelistRecord.fetch(whitelistRecordPDA)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(whitelist.nextEditionNumber.toNumber()).equal(3, "next edition number should be 3")
+        expect(whitelistRecord.minted.toNumber()).equal(2, "minted should be 2")
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasuryBefore + 2 * price.toNumber())
+
+        for (const edition of [1, 2]) {
+            const editionBuffer = Buffer.alloc(8);
+            editionBuffer.writeBigUInt64LE(BigInt(edition));
+            const [mint] = PublicKey.findProgramAddressSync([whitelistPDA.toBuffer(), editionBuffer], program.programId);
+            const tokenAccount = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, buyer.publicKey));
+            expect(Number(tokenAccount.amount)).equal(1, `edition ${edition} should be owned by the buyer`)
+            expect(Number((await getEdition(mint)).edition)).equal(edition)
+            const record = await program.account.whitelistNftRecord.fetch(whitelistEditionRecord(whitelistPDA, edition))
+            expect(record.paidPrice.toNumber()).equal(price.toNumber())
+        }
+
+        try {
+            await buyTx(2, 3);
+            expect.fail("Expected an error but the instruction succeeded");
+        } catch (error: any) {
+            expect(error.message.includes("Error Code: MaxMintPerWalletReached")).true
+        }
+    })
+
+    function whitelistEditionRecord(whitelist: PublicKey, edition: number) {
+        const editionBuffer = Buffer.alloc(8);
+        editionBuffer.writeBigUInt64LE(BigInt(edition));
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("whitelist_nft_record"), whitelist.toBuffer(), editionBuffer],
+            program.programId
+        )[0];
+    }
+
+    async function printEditionAccounts(collection: PublicKey, masterMint: PublicKey, owner: PublicKey, edition: number) {
+        const editionBuffer = Buffer.alloc(8);
+        editionBuffer.writeBigUInt64LE(BigInt(edition));
+        const [mint] = PublicKey.findProgramAddressSync([collection.toBuffer(), editionBuffer], program.programId);
+        const [metadata] = PublicKey.findProgramAddressSync(
+            [Buffer.from('metadata'), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+            METADATA_PROGRAM_ID
+        );
+        const [printEdition] = PublicKey.findProgramAddressSync(
+            [Buffer.from('metadata'), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from('edition')],
+            METADATA_PROGRAM_ID
+        );
+        const [editionMarker] = PublicKey.findProgramAddressSync(
+            [
+                Buffer.from('metadata'),
+                METADATA_PROGRAM_ID.toBuffer(),
+                masterMint.toBuffer(),
+                Buffer.from('edition'),
+                Buffer.from(Math.floor(edition / 248).toString())
+            ],
+            METADATA_PROGRAM_ID
+        );
+        return [mint, await getAssociatedTokenAddress(mint, owner), metadata, printEdition, editionMarker]
+            .map((pubkey) => ({pubkey, isSigner: false, isWritable: true}));
+    }
+
+    /// Whitelist supply and escrow
+    await it("should cap whitelist supply and escrow converted editions", async () => {
+        const holder = Keypair.generate();
+        const lateHolder = Keypair.generate();
+        await fundAccount(holder, 10)
+        await fundAccount(lateHolder, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const price = new anchor.BN(LAMPORTS_PER_SOL);
+
+        await expectError(
+            program.methods.sellWhitelist(
+                "Whitelist Escrow",
+                "WLE",
+                "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+                price,
+                new anchor.BN(now),
+                new anchor.BN(now + 7200),
+                new anchor.BN(0),
+                new anchor.BN(1),
+                new anchor.BN(now),
+                new anchor.BN(now + 600),
+                true,
+                null,
+                null,
+            )
+                .accounts({governanceAuthority: dev.publicKey})
+                .signers([dev])
+                .rpc(),
+            "InvalidSupply"
+        )
+        const events = await rpcWithEvents(
+            program.methods.sellWhitelist(
+                "Whitelist Escrow",
+                "WLE",
+                "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+                price,
+                new anchor.BN(now),
+                new anchor.BN(now + 7200),
+                new anchor.BN(1),
+                new anchor.BN(1),
+                new anchor.BN(now),
+                new anchor.BN(now + 600),
+                true,
+                null,
+                null,
+            )
+                .accounts({governanceAuthority: dev.publicKey}),
+            [dev]
+        );
+        expect(findEvent(events, "WhitelistIssued").escrowOnConversion).equal(true)
+
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const whitelistId = new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1);
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(whitelistId.toNumber()));
+        const [whitelistPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("whitelist"), idBuffer],
+            program.programId
+        );
+
+        const buy = async (buyer: Keypair) => {
+            const tx = new anchor.web3.Transaction();
+            tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+            tx.add(
+                await program.methods
+                    .buyWhitelist(whitelistId, new anchor.BN(1), null)
+                    .accounts({buyer: buyer.publicKey})
+                    .instruction()
+            );
+            return provider.sendAndConfirm(tx, [buyer]);
+        }
+        await buy(holder);
+        try {
+            await buy(lateHolder);
+            expect.fail("Expected an error but the instruction succeeded");
+        } catch (error: any) {
+            expect(error.message.includes("Error Code: MaxSupplyReached")).true
+        }
+
+        const editionBuffer = Buffer.alloc(8);
+        editionBuffer.writeBigUInt64LE(BigInt(1));
+        const [holderWhitelistNFTPDA] = PublicKey.findProgramAddressSync(
+            [whitelistPDA.toBuffer(), editionBuffer],
+            program.programId
+        );
+        const escrowNftAccount = await getAssociatedTokenAddress(holderWhitelistNFTPDA, solStrategyPDA, true);
+
+        await expectError(
+            program.methods.convertWhitelist(whitelistId, new anchor.BN(1), new anchor.BN(0))
+                .accounts({buyer: holder.publicKey, escrowNftAccount: null})
+                .signers([holder])
+                .rpc(),
+            "EscrowAccountRequired"
+        )
+        const events = await rpcWithEvents(
+            program.methods.convertWhitelist(whitelistId, new anchor.BN(1), new anchor.BN(0))
+                .accounts({buyer: holder.publicKey, escrowNftAccount}),
+            [holder]
+        );
+        const converted = findEvent(events, "WhitelistConverted");
+        expect(converted.holder.toBase58()).equal(holder.publicKey.toBase58())
+        expect(converted.whitelistId.toNumber()).equal(whitelistId.toNumber())
+        expect(converted.editionNumber.toNumber()).equal(1)
+        expect(converted.escrowed).equal(true)
+        expect(converted.solxrMinted.toString()).equal(price.toString())
+
+        const holderNftAccount = await getAccount(provider.connection, await getAssociatedTokenAddress(holderWhitelistNFTPDA, holder.publicKey));
+        const escrowAccount = await getAccount(provider.connection, escrowNftAccount);
+        const holderSolxr = await getAccount(provider.connection, await getAssociatedTokenAddress(tokenPDA, holder.publicKey));
+        expect(Number(holderNftAccount.amount)).equal(0, "holder whitelist nft account amount should be 0")
+        expect(Number(escrowAccount.amount)).equal(1, "escrow should hold the converted edition")
+        expect(holderSolxr.amount.toString()).equal(price.toString())
+    })
+
+    /// Bond coupons
+    await it("should pay bond coupons during the bond life", async () => {
+        const holder = Keypair.generate();
+        await fundAccount(holder, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const price = new anchor.BN(LAMPORTS_PER_SOL);
+        const couponRate = new anchor.BN(10_000_000); // 1% per period
+        const couponInterval = new anchor.BN(100);
+        const coupon = price.toNumber() / 100;
+
+        const setTime = async (timestamp: number) => {
+            const clock = await provider.context.banksClient.getClock();
+            provider.context.setClock(
+                new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(timestamp)),
+            );
+        }
+
+        await program.methods.sellBond(
+            "Coupon Bond",
+            "CB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 300),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(10),
+            price,
+            new anchor.BN(1),
+            new anchor.BN(now),
+            new anchor.BN(now + 60),
+            couponRate,
+            couponInterval,
+            new anchor.BN(0),
+            new anchor.BN(now + 1000),
+            null,
+            null,
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondId = new anchor.BN(solStrategy.nextBondId.toNumber() - 1);
+        const solInTreasuryBefore = solStrategy.solInTreasury.toNumber();
+        const solFromBondBefore = solStrategy.solFromBond.toNumber();
+
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+        tx.add(
+            await program.methods
+                .buyBond(bondId, new anchor.BN(1), null)
+                .accounts({buyer: holder.publicKey})
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [holder]);
+
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasuryBefore - 3 * coupon, "coupons should be reserved from the treasury")
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore + price.toNumber() + 3 * coupon)
+
+        await expectError(
+            program.methods.claimCoupon(bondId, new anchor.BN(1))
+                .accounts({holder: holder.publicKey})
+                .signers([holder])
+                .rpc(),
+            "NoCouponDue"
+        )
+
+        await setTime(now + 150);
+        await program.methods.claimCoupon(bondId, new anchor.BN(1))
+            .accounts({holder: holder.publicKey})
+            .signers([holder])
+            .rpc();
+
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(bondId.toNumber()));
+        const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+        const editionBuffer = Buffer.alloc(8);
+        editionBuffer.writeBigUInt64LE(BigInt(1));
+        const [bondEditionRecordPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("bond_edition_record"), bondPDA.toBuffer(), editionBuffer],
+            program.programId
+        );
+        let bondEditionRecord = await program.account.bondEditionRecord.fetch(bondEditionRecordPDA)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(bondEditionRecord.claimedCouponPeriods.toNumber()).equal(1)
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore + price.toNumber() + 2 * coupon)
+
+        // the remaining coupons are paid with the principal
+        await setTime(now + 301);
+        const holderBefore = await provider.connection.getAccountInfo(holder.publicKey);
+        await program.methods.convertBond(bondId, new anchor.BN(1), false, new anchor.BN(0))
+            .accounts({buyer: holder.publicKey})
+            .signers([holder])
+            .rpc();
+        const holderAfter = await provider.connection.getAccountInfo(holder.publicKey);
+
+        bondEditionRecord = await program.account.bondEditionRecord.fetch(bondEditionRecordPDA)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(bondEditionRecord.claimedCouponPeriods.toNumber()).equal(3)
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore)
+        // minus the fee and the rent of the solxr token account opened on redemption
+        expect(holderAfter.lamports - holderBefore.lamports).equal(price.toNumber() + 2 * coupon - 5000 - 2039280)
+    })
+
+    /// Early bond redemption
+    await it("should redeem a bond early with a decaying penalty", async () => {
+        const holder = Keypair.generate();
+        await fundAccount(holder, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const price = new anchor.BN(LAMPORTS_PER_SOL);
+
+        await program.methods.sellBond(
+            "Early Bond",
+            "EB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 1000),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(10),
+            price,
+            new anchor.BN(1),
+            new anchor.BN(now),
+            new anchor.BN(now + 60),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(200_000_000), // 20% at issuance
+            new anchor.BN(now + 2000),
+            null,
+            null,
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondId = new anchor.BN(solStrategy.nextBondId.toNumber() - 1);
+        const solFromBondBefore = solStrategy.solFromBond.toNumber();
+
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+        tx.add(
+            await program.methods
+                .buyBond(bondId, new anchor.BN(1), null)
+                .accounts({buyer: holder.publicKey})
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [holder]);
+
+        // half way to maturity the penalty has decayed to 10%
+        const clock = await provider.context.banksClient.getClock();
+        provider.context.setClock(
+            new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(now + 500)),
+        );
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solInTreasuryBefore = solStrategy.solInTreasury.toNumber();
+
+        await expectError(
+            program.methods.redeemBondEarly(bondId, new anchor.BN(1), new anchor.BN(price.toNumber() * 9 / 10 + 1))
+                .accounts({buyer: holder.publicKey})
+                .signers([holder])
+                .rpc(),
+            "SlippageExceeded"
+        )
+        await program.methods.redeemBondEarly(bondId, new anchor.BN(1), new anchor.BN(price.toNumber() * 9 / 10))
+            .accounts({buyer: holder.publicKey})
+            .signers([holder])
+            .rpc();
+
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasuryBefore + price.toNumber() / 10, "penalty should stay in the treasury")
+
+        await expectError(
+            program.methods.redeemBondEarly(bondId, new anchor.BN(1), new anchor.BN(0))
+                .accounts({buyer: holder.publicKey})
+                .signers([holder])
+                .rpc(),
+            "InvalidTokenAmount"
+        )
+    })
+
+    /// Bond redemption deadline
+    await it("should close conversion after the deadline and keep Sol redemption open", async () => {
+        const holder = Keypair.generate();
+        await fundAccount(holder, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const price = new anchor.BN(LAMPORTS_PER_SOL);
+        const setTime = async (timestamp: number) => {
+            const clock = await provider.context.banksClient.getClock();
+            provider.context.setClock(
+                new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(timestamp)),
+            );
+        }
+
+        await program.methods.sellBond(
+            "Deadline Bond",
+            "DB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 100),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(10),
+            price,
+            new anchor.BN(1),
+            new anchor.BN(now),
+            new anchor.BN(now + 60),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(now + 200),
+            null,
+            null,
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondId = new anchor.BN(solStrategy.nextBondId.toNumber() - 1);
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+        tx.add(
+            await program.methods
+                .buyBond(bondId, new anchor.BN(1), null)
+                .accounts({buyer: holder.publicKey})
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [holder]);
+
+        await setTime(now + 250);
+        await expectError(
+            program.methods.convertBond(bondId, new anchor.BN(1), true, new anchor.BN(0))
+                .accounts({buyer: holder.publicKey})
+                .signers([holder])
+                .rpc(),
+            "ConversionWindowClosed"
+        )
+
+        // the holder can still get the Sol back long after the deadline
+        await setTime(now + 10 * 365 * 24 * 3600);
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solInTreasuryBefore = solStrategy.solInTreasury.toNumber();
+        const solFromBondBefore = solStrategy.solFromBond.toNumber();
+        const holderBefore = await provider.connection.getAccountInfo(holder.publicKey);
+        await program.methods.convertBond(bondId, new anchor.BN(1), false, price)
+            .accounts({buyer: holder.publicKey})
+            .signers([holder])
+            .rpc();
+        const holderAfter = await provider.connection.getAccountInfo(holder.publicKey);
+
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(bondId.toNumber()));
+        const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+        const bond = await program.account.bond.fetch(bondPDA)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(bond.solReserved.toNumber()).equal(0)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasuryBefore)
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore - price.toNumber())
+        // minus the fee and the rent of the solxr token account opened on redemption
+        expect(holderAfter.lamports - holderBefore.lamports).equal(price.toNumber() - 5000 - 2039280)
+        await setTime(now);
+    })
+
+    /// Bond edition status
+    await it("should record the outcome of a bond edition", async () => {
+        const holder = Keypair.generate();
+        await fundAccount(holder, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+
+        await program.methods.sellBond(
+            "Status Bond",
+            "SB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 100),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(10),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(1),
+            new anchor.BN(now),
+            new anchor.BN(now + 60),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(now + 200),
+            null,
+            null,
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondId = new anchor.BN(solStrategy.nextBondId.toNumber() - 1);
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+        tx.add(
+            await program.methods
+                .buyBond(bondId, new anchor.BN(1), null)
+                .accounts({buyer: holder.publicKey})
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [holder]);
+
+        let status = await program.methods.getBondEditionStatus(bondId, new anchor.BN(1)).view()
+        expect(status.converted).equal(false)
+        expect(status.outcome).deep.equal({outstanding: {}})
+        await expectError(
+            program.methods.getBondEditionStatus(bondId, new anchor.BN(2)).view(),
+            "AccountNotInitialized"
+        )
+
+        const clock = await provider.context.banksClient.getClock();
+        provider.context.setClock(
+            new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(now + 150)),
+        );
+        await expectError(
+            program.methods.convertBond(bondId, new anchor.BN(1), false, new anchor.BN(LAMPORTS_PER_SOL + 1))
+                .accounts({buyer: holder.publicKey})
+                .signers([holder])
+                .rpc(),
+            "SlippageExceeded"
+        )
+        await program.methods.convertBond(bondId, new anchor.BN(1), false, new anchor.BN(LAMPORTS_PER_SOL))
+            .accounts({buyer: holder.publicKey})
+            .signers([holder])
+            .rpc();
+
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(bondId.toNumber()));
+        const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+        const editionBuffer = Buffer.alloc(8);
+        editionBuffer.writeBigUInt64LE(BigInt(1));
+        const [bondEditionRecordPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("bond_edition_record"), bondPDA.toBuffer(), editionBuffer],
+            program.programId
+        );
+        const record = await program.account.bondEditionRecord.fetch(bondEditionRecordPDA)
+        expect(record.converted).equal(true)
+        expect(record.converter.toBase58()).equal(holder.publicKey.toBase58())
+        expect(record.convertedAt.toNumber()).equal(now + 150)
+        expect(record.outcome).deep.equal({sol: {}})
+
+        status = await program.methods.getBondEditionStatus(bondId, new anchor.BN(1)).view()
+        expect(status.outcome).deep.equal({sol: {}})
+
+        await expectError(
+            program.methods.convertBond(bondId, new anchor.BN(1), true, new anchor.BN(0))
+                .accounts({buyer: holder.publicKey})
+                .signers([holder])
+                .rpc(),
+            "InvalidTokenAmount"
+        )
+    })
+
+    /// Strike price derived from NAV
+    await it("should derive the bond strike price from NAV", async () => {
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const conversionPremium = new anchor.BN(250_000_000); // 25% above NAV
+
+        await program.methods.sellBond(
+            "NAV Bond",
+            "NB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 100),
+            new anchor.BN(1), // ignored when the strike is derived from NAV
+            new anchor.BN(10),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(1),
+            new anchor.BN(now),
+            new anchor.BN(now + 60),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(now + 200),
+            conversionPremium,
+            null,
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solxr = await getMint(provider.connection, tokenPDA)
+        const nav = BigInt(solStrategy.solInTreasury.toString()) * BigInt(LAMPORTS_PER_SOL) / solxr.supply;
+        const expectedStrike = nav * BigInt(LAMPORTS_PER_SOL + conversionPremium.toNumber()) / BigInt(LAMPORTS_PER_SOL);
+
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(solStrategy.nextBondId.toNumber() - 1));
+        const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+        const bond = await program.account.bond.fetch(bondPDA)
+        expect(bond.issuanceNav.toString()).equal(nav.toString())
+        expect(bond.strikePrice.toString()).equal(expectedStrike.toString())
+        expect(bond.conversionPremium.toNumber()).equal(conversionPremium.toNumber())
+    })
+
+    /// Dutch auction
+    await it("should charge the auction price and return it on redemption", async () => {
+        const buyer = Keypair.generate();
+        await fundAccount(buyer, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const setTime = async (timestamp: number) => {
+            const clock = await provider.context.banksClient.getClock();
+            provider.context.setClock(
+                new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(timestamp)),
+            );
+        }
+
+        await expectError(
+            program.methods.sellBond(
+                "Auction Bond",
+                "AB",
+                "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+                new anchor.BN(now + 200),
+                new anchor.BN(LAMPORTS_PER_SOL),
+                new anchor.BN(10),
+                new anchor.BN(LAMPORTS_PER_SOL),
+                new anchor.BN(2),
+                new anchor.BN(now),
+                new anchor.BN(now + 100),
+                new anchor.BN(0),
+                new anchor.BN(0),
+                new anchor.BN(0),
+                new anchor.BN(now + 300),
+                null,
+                {startPrice: new anchor.BN(LAMPORTS_PER_SOL), floorPrice: new anchor.BN(2 * LAMPORTS_PER_SOL), decay: {linear: {}}},
+                null,
+            )
+                .accounts({governanceAuthority: dev.publicKey})
+                .signers([dev])
+                .rpc(),
+            "InvalidAuction"
+        )
+
+        await program.methods.sellBond(
+            "Auction Bond",
+            "AB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 200),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(10),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(2),
+            new anchor.BN(now),
+            new anchor.BN(now + 100),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(0),
+            new anchor.BN(now + 300),
+            null,
+            {startPrice: new anchor.BN(2 * LAMPORTS_PER_SOL), floorPrice: new anchor.BN(LAMPORTS_PER_SOL), decay: {linear: {}}},
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondId = new anchor.BN(solStrategy.nextBondId.toNumber() - 1);
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(bondId.toNumber()));
+        const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+        const [bondNFTPDA] = PublicKey.findProgramAddressSync([bondPDA.toBuffer()], program.programId);
+        const editionRecord = (edition: number) => {
+            const editionBuffer = Buffer.alloc(8);
+            editionBuffer.writeBigUInt64LE(BigInt(edition));
+            return PublicKey.findProgramAddressSync(
+                [Buffer.from("bond_edition_record"), bondPDA.toBuffer(), editionBuffer],
+                program.programId
+            )[0];
+        }
+
+        // half way through the sale the price is down to 1.5 SOL
+        await setTime(now + 50);
+        const auctionPrice = 1.5 * LAMPORTS_PER_SOL;
+        const solFromBondBefore = solStrategy.solFromBond.toNumber();
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 600_000}));
+        tx.add(
+            await program.methods
+                .buyBond(bondId, new anchor.BN(2), null)
+                .accounts({buyer: buyer.publicKey})
+                .remainingAccounts([
+                    ...await printEditionAccounts(bondPDA, bondNFTPDA, buyer.publicKey, 2),
+                    {pubkey: editionRecord(2), isSigner: false, isWritable: true},
+                ])
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [buyer]);
+
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore + 2 * auctionPrice)
+        for (const edition of [1, 2]) {
+            const record = await program.account.bondEditionRecord.fetch(editionRecord(edition))
+            expect(record.editionNumber.toNumber()).equal(edition)
+            expect(record.paidPrice.toNumber()).equal(auctionPrice, `edition ${edition} should record the auction price`)
+        }
+
+        await setTime(now + 200);
+        const buyerBefore = await provider.connection.getAccountInfo(buyer.publicKey);
+        await program.methods.convertBond(bondId, new anchor.BN(2), false, new anchor.BN(0))
+            .accounts({buyer: buyer.publicKey})
+            .signers([buyer])
+            .rpc();
+        const buyerAfter = await provider.connection.getAccountInfo(buyer.publicKey);
+        // minus the fee and the rent of the solxr token account opened on redemption
+        expect(buyerAfter.lamports - buyerBefore.lamports).equal(auctionPrice - 5000 - 2039280)
+    })
+
+    /// Mint round price curve
+    await it("should raise the mint round price along a bonding curve", async () => {
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solxr = await getMint(provider.connection, tokenPDA)
+        const nav = BigInt(solStrategy.solInTreasury.toString()) * BigInt(LAMPORTS_PER_SOL) / solxr.supply;
+        const premium = nav * BigInt(3);
+        const roundID = new anchor.BN(solStrategy.nextMintingRounds.toNumber());
+
+        await expectError(
+            program.methods.openMintRound(roundID, new anchor.BN(premium.toString()), {bonding: {maxPremium: new anchor.BN((premium - BigInt(1)).toString())}}, null)
+                .accounts({governanceAuthority: dev.publicKey, priceOracle: null, priceObservation: null})
+                .signers([dev])
+                .rpc(),
+            "InvalidPriceCurve"
+        )
+        await program.methods.openMintRound(roundID, new anchor.BN(premium.toString()), {bonding: {maxPremium: new anchor.BN((premium * BigInt(2)).toString())}}, null)
+            .accounts({governanceAuthority: dev.publicKey, priceOracle: null, priceObservation: null})
+            .signers([dev])
+            .rpc();
+
+        const amount = new anchor.BN(LAMPORTS_PER_SOL);
+        const buy = async (investor: Keypair, minSolxrOut: anchor.BN) => {
+            const events = await rpcWithEvents(
+                program.methods.buySolxr(roundID, amount, minSolxrOut, null)
+                    .accounts({investor: investor.publicKey, platformAddress: platformDesignatedAccount.publicKey}),
+                [investor]
+            );
+            const account = await getAccount(provider.connection, await getAssociatedTokenAddress(tokenPDA, investor.publicKey));
+            const minted = findEvent(events, "SolxrMinted");
+            const platformFee = BigInt(amount.toString()) * BigInt(solStrategy.platformMintFee.toString()) / BigInt(LAMPORTS_PER_SOL);
+            expect(minted.investor.toBase58()).equal(investor.publicKey.toBase58())
+            expect(minted.roundId.toNumber()).equal(roundID.toNumber())
+            expect(minted.solAmount.toString()).equal(amount.toString())
+            expect(minted.platformFee.toString()).equal(platformFee.toString())
+            expect(minted.premium.toString()).equal(premium.toString())
+            expect(minted.solxrMinted.toString()).equal(account.amount.toString())
+            return Number(account.amount);
+        }
+
+        const firstInvestor = Keypair.generate();
+        const secondInvestor = Keypair.generate();
+        await fundAccount(firstInvestor, 10)
+        await fundAccount(secondInvestor, 10)
+
+        const firstSolxr = await buy(firstInvestor, new anchor.BN(0));
+        const fixedPriceSolxr = Number(BigInt(amount.toString()) * BigInt(LAMPORTS_PER_SOL - solStrategy.platformMintFee.toNumber()) / BigInt(LAMPORTS_PER_SOL) * BigInt(LAMPORTS_PER_SOL) / premium);
+        expect(firstSolxr).lessThan(fixedPriceSolxr, "the price should rise while the purchase is minted")
+
+        await expectError(
+            program.methods.buySolxr(roundID, amount, new anchor.BN(firstSolxr), null)
+                .accounts({investor: secondInvestor.publicKey, platformAddress: platformDesignatedAccount.publicKey})
+                .signers([secondInvestor])
+                .rpc(),
+            "SlippageExceeded"
+        )
+        const secondSolxr = await buy(secondInvestor, new anchor.BN(0));
+        expect(secondSolxr).lessThan(firstSolxr, "later investors should pay a higher price")
+
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(roundID.toNumber()));
+        const [mintRoundPDA] = PublicKey.findProgramAddressSync([Buffer.from("mint_round"), idBuffer], program.programId);
+        const mintRound = await program.account.mintRound.fetch(mintRoundPDA)
+        expect(mintRound.solxrMinted.toNumber()).equal(firstSolxr + secondSolxr)
+        const platformFee = amount.toNumber() * solStrategy.platformMintFee.toNumber() / LAMPORTS_PER_SOL;
+        const newSolStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(newSolStrategy.solInTreasury.toNumber()).equal(solStrategy.solInTreasury.toNumber() + 2 * (amount.toNumber() - platformFee), "minted Sol should be added to the treasury")
+
+        await closeMintingRound(dev)
+    })
+
+    /// Merkle allowlist
+    await it("should gate whitelist purchases with a Merkle allowlist", async () => {
+        const allowed = Keypair.generate();
+        const other = Keypair.generate();
+        const outsider = Keypair.generate();
+        await fundAccount(allowed, 10)
+        await fundAccount(outsider, 10)
+        const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+        const leaf = (address: PublicKey, allocation: number) => {
+            const allocationBuffer = Buffer.alloc(8);
+            allocationBuffer.writeBigUInt64LE(BigInt(allocation));
+            return sha256(address.toBuffer(), allocationBuffer);
+        }
+        const allowedLeaf = leaf(allowed.publicKey, 1);
+        const otherLeaf = leaf(other.publicKey, 3);
+        const root = Buffer.compare(allowedLeaf, otherLeaf) <= 0 ? sha256(allowedLeaf, otherLeaf) : sha256(otherLeaf, allowedLeaf);
+        const proof = (allocation: number) => ({allocation: new anchor.BN(allocation), proof: [Array.from(otherLeaf)]});
+
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        await program.methods.sellWhitelist(
+            "Allowlist Whitelist",
+            "AWL",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(now),
+            new anchor.BN(now + 7200),
+            new anchor.BN(10),
+            new anchor.BN(5),
+            new anchor.BN(now),
+            new anchor.BN(now + 600),
+            false,
+            null,
+            Array.from(root),
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const whitelistId = new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1);
+        const buy = async (buyer: Keypair, allowlist: any) => {
+            const tx = new anchor.web3.Transaction();
+            tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+            tx.add(
+                await program.methods
+                    .buyWhitelist(whitelistId, new anchor.BN(1), allowlist)
+                    .accounts({buyer: buyer.publicKey})
+                    .instruction()
+            );
+            return provider.sendAndConfirm(tx, [buyer]);
+        }
+        const expectBuyError = async (buyer: Keypair, allowlist: any, errorCode: string) => {
+            try {
+                await buy(buyer, allowlist);
+                expect.fail("Expected an error but the instruction succeeded");
+            } catch (error: any) {
+                expect(error.message.includes(`Error Code: ${errorCode}`)).true
+            }
+        }
+
+        await expectBuyError(allowed, null, "AllowlistProofRequired")
+        await expectBuyError(allowed, proof(2), "InvalidAllowlistProof")
+        await expectBuyError(outsider, proof(1), "InvalidAllowlistProof")
+        await buy(allowed, proof(1));
+        await expectBuyError(allowed, proof(1), "ExceedsAllocation")
+    })
+
+    /// Native staking
+    await it("should stake treasury SOL and count the rewards in NAV", async () => {
+        const node = Keypair.generate();
+        const voteAccount = Keypair.generate();
+        await provider.sendAndConfirm(
+            new Transaction().add(VoteProgram.createAccount({
+                fromPubkey: dev.publicKey,
+                votePubkey: voteAccount.publicKey,
+                voteInit: new VoteInit(node.publicKey, node.publicKey, node.publicKey, 0),
+                lamports: LAMPORTS_PER_SOL,
+            })),
+            [dev, voteAccount, node]
+        );
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const stakeId = solStrategy.nextStakeId;
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(stakeId.toNumber()));
+        const [nativeStakePDA] = PublicKey.findProgramAddressSync([Buffer.from("native_stake"), idBuffer], program.programId);
+        const [stakeAccountPDA] = PublicKey.findProgramAddressSync([Buffer.from("stake_account"), idBuffer], program.programId);
+        const solInTreasury = solStrategy.solInTreasury.toNumber();
+        const stakedLamports = solStrategy.stakedLamports.toNumber();
+
+        const amount = 10 * LAMPORTS_PER_SOL;
+        await program.methods.createStake(new anchor.BN(amount))
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasury - amount)
+        expect(solStrategy.stakedLamports.toNumber()).equal(stakedLamports + amount)
+        let nativeStake = await program.account.nativeStake.fetch(nativeStakePDA)
+        expect(nativeStake.stakeAccount.toBase58()).equal(stakeAccountPDA.toBase58())
+        expect(nativeStake.voteAccount).null
+
+        try {
+            await program.methods.deactivateStake(stakeId)
+                .accounts({governanceAuthority: dev.publicKey, stakeAccount: stakeAccountPDA})
+                .signers([dev])
+                .rpc();
+            expect.fail("Expected an error but the instruction succeeded");
+        } catch (error: any) {
+            expect(error.message.includes("Error Code: StakeNotDelegated")).true
+        }
+
+        await program.methods.delegateStake(stakeId)
+            .accounts({governanceAuthority: dev.publicKey, stakeAccount: stakeAccountPDA, voteAccount: voteAccount.publicKey})
+            .signers([dev])
+            .rpc();
+        nativeStake = await program.account.nativeStake.fetch(nativeStakePDA)
+        expect(nativeStake.voteAccount.toBase58()).equal(voteAccount.publicKey.toBase58())
+
+        // rewards are paid into the stake account, anyone can count them in NAV
+        const rewards = LAMPORTS_PER_SOL;
+        await provider.sendAndConfirm(
+            new Transaction().add(SystemProgram.transfer({fromPubkey: dev.publicKey, toPubkey: stakeAccountPDA, lamports: rewards})),
+            [dev]
+        );
+        await program.methods.syncStake(stakeId)
+            .accounts({stakeAccount: stakeAccountPDA})
+            .rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.stakedLamports.toNumber()).equal(stakedLamports + amount + rewards)
+        expect(solStrategy.solInTreasury.toNumber() + solStrategy.stakedLamports.toNumber()).equal(solInTreasury + stakedLamports + rewards)
+
+        await program.methods.deactivateStake(stakeId)
+            .accounts({governanceAuthority: dev.publicKey, stakeAccount: stakeAccountPDA})
+            .signers([dev])
+            .rpc();
+        // deactivated in the epoch it was delegated, the stake is withdrawable right away
+        await program.methods.withdrawStake(stakeId)
+            .accounts({governanceAuthority: dev.publicKey, stakeAccount: stakeAccountPDA})
+            .signers([dev])
+            .rpc();
+
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasury + rewards)
+        expect(solStrategy.stakedLamports.toNumber()).equal(stakedLamports)
+        expect(await program.account.nativeStake.fetchNullable(nativeStakePDA)).null
+        expect(await provider.connection.getAccountInfo(stakeAccountPDA)).null
+    })
+
+    /// SPL stake pool
+    await it("should deposit treasury SOL into a stake pool and value the pool tokens", async () => {
+        const stakePool = Keypair.generate();
+        const validatorList = Keypair.generate();
+        const reserveStake = Keypair.generate();
+        const poolMint = Keypair.generate();
+        const [withdrawAuthority] = PublicKey.findProgramAddressSync(
+            [stakePool.publicKey.toBuffer(), Buffer.from("withdraw")],
+            STAKE_POOL_PROGRAM_ID
+        );
+        const rent = await context.banksClient.getRent();
+        const rentFor = (space: number) => Number(rent.minimumBalance(BigInt(space)));
+        const createAccount = (account: Keypair, space: number, programId: PublicKey, extraLamports = 0) =>
+            SystemProgram.createAccount({
+                fromPubkey: dev.publicKey,
+                newAccountPubkey: account.publicKey,
+                space,
+                lamports: rentFor(space) + extraLamports,
+                programId,
+            });
+
+        // pool mint and reserve are controlled by the pool withdraw authority, dev manages the pool
+        const managerFeeAccount = await getAssociatedTokenAddress(poolMint.publicKey, dev.publicKey);
+        const maxValidators = 10;
+        const validatorListSpace = 5 + 4 + 73 * maxValidators;
+        await provider.sendAndConfirm(
+            new Transaction().add(
+                createAccount(poolMint, MINT_SIZE, TOKEN_PROGRAM_ID),
+                createInitializeMint2Instruction(poolMint.publicKey, 9, withdrawAuthority, null),
+                createAssociatedTokenAccountInstruction(dev.publicKey, managerFeeAccount, dev.publicKey, poolMint.publicKey),
+                StakeProgram.createAccount({
+                    fromPubkey: dev.publicKey,
+                    stakePubkey: reserveStake.publicKey,
+                    authorized: new Authorized(withdrawAuthority, withdrawAuthority),
+                    lamports: rentFor(StakeProgram.space) + LAMPORTS_PER_SOL,
+                }),
+                createAccount(validatorList, validatorListSpace, STAKE_POOL_PROGRAM_ID),
+                createAccount(stakePool, 611, STAKE_POOL_PROGRAM_ID),
+            ),
+            [dev, poolMint, reserveStake, validatorList, stakePool]
+        );
+        // zero fees, a referral fee of zero and room for ten validators
+        const initializeData = Buffer.alloc(1 + 3 * 16 + 1 + 4);
+        for (let fee = 0; fee < 3; fee++) {
+            initializeData.writeBigUInt64LE(BigInt(100), 1 + fee * 16);
+        }
+        initializeData.writeUInt32LE(maxValidators, 1 + 3 * 16 + 1);
+        await provider.sendAndConfirm(
+            new Transaction().add(new TransactionInstruction({
+                programId: STAKE_POOL_PROGRAM_ID,
+                keys: [
+                    {pubkey: stakePool.publicKey, isSigner: false, isWritable: true},
+                    {pubkey: dev.publicKey, isSigner: true, isWritable: false},
+                    {pubkey: dev.publicKey, isSigner: false, isWritable: false},
+                    {pubkey: withdrawAuthority, isSigner: false, isWritable: false},
+                    {pubkey: validatorList.publicKey, isSigner: false, isWritable: true},
+                    {pubkey: reserveStake.publicKey, isSigner: false, isWritable: false},
+                    {pubkey: poolMint.publicKey, isSigner: false, isWritable: true},
+                    {pubkey: managerFeeAccount, isSigner: false, isWritable: true},
+                    {pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false},
+                ],
+                data: initializeData,
+            })),
+            [dev]
+        );
+        const poolAccounts = {
+            governanceAuthority: dev.publicKey,
+            stakePool: stakePool.publicKey,
+            stakePoolWithdrawAuthority: withdrawAuthority,
+            reserveStake: reserveStake.publicKey,
+            managerFeeAccount,
+            poolMint: poolMint.publicKey,
+        };
+        const poolValue = async (poolTokens: bigint) => {
+            const data = (await provider.connection.getAccountInfo(stakePool.publicKey)).data;
+            return Number(poolTokens * data.readBigUInt64LE(258) / data.readBigUInt64LE(266));
+        }
+
+        await executeGovernanceAction({setStakePool: {stakePool: stakePool.publicKey}});
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solInTreasury = solStrategy.solInTreasury.toNumber();
+        const deposit = 5 * LAMPORTS_PER_SOL;
+        await program.methods.depositStakePool(new anchor.BN(deposit), new anchor.BN(deposit))
+            .accounts(poolAccounts)
+            .signers([dev])
+            .rpc();
+
+        const [treasuryPDA] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], program.programId);
+        const poolTokenAccount = await getAssociatedTokenAddress(poolMint.publicKey, treasuryPDA, true);
+        let poolTokens = (await getAccount(provider.connection, poolTokenAccount)).amount;
+        expect(Number(poolTokens)).equal(deposit)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasury - deposit)
+        expect(solStrategy.stakePoolLamports.toNumber()).equal(deposit)
+
+        // the pool can't be replaced while the treasury holds pool tokens, even with an unsynced value
+        const {unixTimestamp} = await provider.context.banksClient.getClock();
+        const {id, eta} = await queueGovernanceAction({setStakePool: {stakePool: null}});
+        await setUnixTimestamp(BigInt(eta.toString()));
+        await expectError(
+            program.methods.executeGovernanceAction(id)
+                .accounts({proposer: dev.publicKey, stakePool: null, poolTokenAccount: null})
+                .rpc(),
+            "StakePoolAccountsRequired"
+        )
+        const strategyAccount = await provider.connection.getAccountInfo(solStrategyPDA);
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const staleData = await program.coder.accounts.encode("SolStrategy", {...solStrategy, stakePoolLamports: new anchor.BN(0)});
+        provider.context.setAccount(solStrategyPDA, {...strategyAccount, data: Buffer.concat([staleData, strategyAccount.data.subarray(staleData.length)])});
+        await expectError(
+            program.methods.executeGovernanceAction(id)
+                .accounts({proposer: dev.publicKey, stakePool: stakePool.publicKey, poolTokenAccount})
+                .rpc(),
+            "StakePoolInUse"
+        )
+        provider.context.setAccount(solStrategyPDA, strategyAccount);
+        await setUnixTimestamp(unixTimestamp);
+        await program.methods.cancelGovernanceAction(id)
+            .accounts({governanceAuthority: dev.publicKey, proposer: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        // rewards land in the reserve and are picked up by the pool balance update
+        await provider.sendAndConfirm(
+            new Transaction().add(
+                SystemProgram.transfer({fromPubkey: dev.publicKey, toPubkey: reserveStake.publicKey, lamports: LAMPORTS_PER_SOL}),
+                new TransactionInstruction({
+                    programId: STAKE_POOL_PROGRAM_ID,
+                    keys: [
+                        {pubkey: stakePool.publicKey, isSigner: false, isWritable: true},
+                        {pubkey: withdrawAuthority, isSigner: false, isWritable: false},
+                        {pubkey: validatorList.publicKey, isSigner: false, isWritable: true},
+                        {pubkey: reserveStake.publicKey, isSigner: false, isWritable: false},
+                        {pubkey: managerFeeAccount, isSigner: false, isWritable: true},
+                        {pubkey: poolMint.publicKey, isSigner: false, isWritable: true},
+                        {pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false},
+                    ],
+                    data: Buffer.from([7]),
+                }),
+            ),
+            [dev]
+        );
+        await program.methods.syncStakePool()
+            .accounts({stakePool: stakePool.publicKey, poolMint: poolMint.publicKey})
+            .rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const valueWithRewards = await poolValue(poolTokens);
+        expect(valueWithRewards).greaterThan(deposit)
+        expect(solStrategy.stakePoolLamports.toNumber()).equal(valueWithRewards)
+
+        const withdrawn = BigInt(2 * LAMPORTS_PER_SOL);
+        const expectedLamports = await poolValue(withdrawn);
+        await program.methods.withdrawStakePool(new anchor.BN(withdrawn.toString()), new anchor.BN(expectedLamports))
+            .accounts(poolAccounts)
+            .signers([dev])
+            .rpc();
+
+        poolTokens = (await getAccount(provider.connection, poolTokenAccount)).amount;
+        expect(poolTokens).equal(BigInt(deposit) - withdrawn)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasury - deposit + expectedLamports)
+        expect(solStrategy.stakePoolLamports.toNumber()).equal(await poolValue(poolTokens))
+    })
+
+    /// Treasury reconciliation
+    await it("should credit treasury donations to NAV and reject a shortfall", async () => {
+        const [treasuryPDA] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], program.programId);
+        await program.methods.syncTreasury().rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solInTreasury = solStrategy.solInTreasury.toNumber();
+        const donation = 2 * LAMPORTS_PER_SOL;
+        await provider.sendAndConfirm(
+            new Transaction().add(SystemProgram.transfer({fromPubkey: dev.publicKey, toPubkey: treasuryPDA, lamports: donation})),
+            [dev]
+        );
+        await program.methods.syncTreasury().rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasury + donation, "the donation should be credited to NAV")
+
+        // an already synced treasury has no surplus left
+        await program.methods.syncTreasury().rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toNumber()).equal(solInTreasury + donation)
+
+        const treasury = await provider.connection.getAccountInfo(treasuryPDA);
+        provider.context.setAccount(treasuryPDA, {...treasury, lamports: treasury.lamports - 1});
+        await expectError(program.methods.syncTreasury().rpc(), "TreasuryShortfall")
+        provider.context.setAccount(treasuryPDA, treasury);
+    })
+
+    /// Solvency invariants
+    await it("should check the solvency invariants and pause on a violation", async () => {
+        const [treasuryPDA] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], program.programId);
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        await program.methods.checkInvariants(false).rpc();
+
+        const treasury = await provider.connection.getAccountInfo(treasuryPDA);
+        provider.context.setAccount(treasuryPDA, {...treasury, lamports: treasury.lamports - 1});
+        await expectError(program.methods.checkInvariants(false).rpc(), "InvariantViolated")
+        const oldPaused = solStrategy.paused;
+        await program.methods.checkInvariants(true).rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).equal(0x3f, "a violation should pause the whole protocol")
+
+        provider.context.setAccount(treasuryPDA, treasury);
+        await program.methods.setPause(oldPaused)
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        await program.methods.checkInvariants(true).rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).equal(oldPaused)
+    })
+
+    /// Bond reserves
+    await it("should check the reserve of each bond against its edition records page by page", async () => {
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondAddress = (id: number) => {
+            const idBuffer = Buffer.alloc(8);
+            idBuffer.writeBigUInt64LE(BigInt(id));
+            return PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId)[0];
+        }
+        const editionRecords = (bondPDA: PublicKey, from: number, to: number) => {
+            const records = [];
+            for (let edition = from; edition < to; edition++) {
+                const editionBuffer = Buffer.alloc(8);
+                editionBuffer.writeBigUInt64LE(BigInt(edition));
+                const [record] = PublicKey.findProgramAddressSync(
+                    [Buffer.from("bond_edition_record"), bondPDA.toBuffer(), editionBuffer],
+                    program.programId
+                );
+                records.push({pubkey: record, isWritable: false, isSigner: false});
+            }
+            return records;
+        }
+        const checkBondReserves = (id: number, records: any[], pauseOnViolation = false) =>
+            program.methods.checkBondReserves(new anchor.BN(id), pauseOnViolation)
+                .accounts({payer: provider.wallet.publicKey})
+                .remainingAccounts(records)
+                .rpc();
+
+        // every bond is audited on its own, two edition records per transaction
+        let auditedBond = 0;
+        let mostEditions = 0;
+        for (let id = 1; id < solStrategy.nextBondId.toNumber(); id++) {
+            const bondPDA = bondAddress(id);
+            const bond = await program.account.bond.fetch(bondPDA)
+            const nextEdition = bond.nextEditionNumber.toNumber();
+            for (let edition = 1; edition < nextEdition || edition == 1; edition += 2) {
+                await checkBondReserves(id, editionRecords(bondPDA, edition, Math.min(edition + 2, nextEdition)));
+            }
+            if (nextEdition - 1 > mostEditions) {
+                auditedBond = id;
+                mostEditions = nextEdition - 1;
+            }
+        }
+        expect(mostEditions).greaterThan(1, "a bond with several editions should have been audited")
+
+        const bondPDA = bondAddress(auditedBond);
+        const [auditPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond_reserve_audit"), bondPDA.toBuffer()], program.programId);
+        let audit = await program.account.bondReserveAudit.fetch(auditPDA)
+        expect(audit.nextEditionNumber.toNumber()).equal(0, "a completed audit should start over")
+
+        await expectError(checkBondReserves(auditedBond, editionRecords(bondPDA, 2, 3)), "InvalidEditionRecord")
+        await checkBondReserves(auditedBond, editionRecords(bondPDA, 1, 2));
+        audit = await program.account.bondReserveAudit.fetch(auditPDA)
+        expect(audit.nextEditionNumber.toNumber()).equal(2)
+
+        // a bond reserving less than its editions are owed is reported once every record is counted
+        const bondAccount = await provider.connection.getAccountInfo(bondPDA);
+        const bond = await program.account.bond.fetch(bondPDA)
+        const data = await program.coder.accounts.encode("Bond", {...bond, solReserved: bond.solReserved.subn(1)});
+        provider.context.setAccount(bondPDA, {...bondAccount, data: Buffer.concat([data, bondAccount.data.subarray(data.length)])});
+        const remaining = editionRecords(bondPDA, 2, bond.nextEditionNumber.toNumber());
+        await expectError(checkBondReserves(auditedBond, remaining), "InvariantViolated")
+
+        const oldPaused = solStrategy.paused;
+        await checkBondReserves(auditedBond, remaining, true);
+        let strategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(strategy.paused).equal(0x3f, "a violation should pause the whole protocol")
+
+        provider.context.setAccount(bondPDA, bondAccount);
+        await program.methods.setPause(oldPaused)
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        await checkBondReserves(auditedBond, editionRecords(bondPDA, 1, bond.nextEditionNumber.toNumber()), true);
+        strategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(strategy.paused).equal(oldPaused)
+    })
+
+    /// Sol from bond
+    await it("should check that the bond reserves add up to sol from bond page by page", async () => {
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bonds = [];
+        for (let id = 1; id < solStrategy.nextBondId.toNumber(); id++) {
+            const idBuffer = Buffer.alloc(8);
+            idBuffer.writeBigUInt64LE(BigInt(id));
+            const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+            bonds.push({pubkey: bondPDA, isWritable: false, isSigner: false});
+        }
+        expect(bonds.length).greaterThan(2)
+        const checkSolFromBond = (page: any[], pauseOnViolation = false) =>
+            program.methods.checkSolFromBond(pauseOnViolation)
+                .accounts({payer: provider.wallet.publicKey})
+                .remainingAccounts(page)
+                .rpc();
+        const [auditPDA] = PublicKey.findProgramAddressSync([Buffer.from("sol_from_bond_audit")], program.programId);
+
+        // two bonds per transaction
+        for (let index = 0; index < bonds.length; index += 2) {
+            await checkSolFromBond(bonds.slice(index, index + 2));
+        }
+        let audit = await program.account.solFromBondAudit.fetch(auditPDA)
+        expect(audit.nextBondId.toNumber()).equal(0, "a completed audit should start over")
+
+        await expectError(checkSolFromBond(bonds.slice(1, 2)), "InvalidBondAccount")
+        await checkSolFromBond(bonds.slice(0, 1));
+        audit = await program.account.solFromBondAudit.fetch(auditPDA)
+        expect(audit.nextBondId.toNumber()).equal(2)
+
+        // a drift of sol from bond is reported once every bond is counted
+        const account = await provider.connection.getAccountInfo(solStrategyPDA);
+        const data = await program.coder.accounts.encode("SolStrategy", {...solStrategy, solFromBond: solStrategy.solFromBond.addn(1)});
+        provider.context.setAccount(solStrategyPDA, {...account, data: Buffer.concat([data, account.data.subarray(data.length)])});
+        await expectError(checkSolFromBond(bonds.slice(1)), "InvariantViolated")
+
+        await checkSolFromBond(bonds.slice(1), true);
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).equal(0x3f, "a violation should pause the whole protocol")
+
+        provider.context.setAccount(solStrategyPDA, account);
+        await checkSolFromBond(bonds, true);
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.paused).not.equal(0x3f)
+    })
+
+    /// Governance timelock
+    await it("should only apply sensitive governance actions after the timelock", async () => {
+        const badActor = Keypair.generate();
+        await fundAccount(badActor, 10)
+        const {unixTimestamp} = await provider.context.banksClient.getClock();
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const governanceDelay = solStrategy.governanceDelay;
+        const platformMintFee = solStrategy.platformMintFee;
+        const action = strategyParams({platformMintFee: new anchor.BN(25_000_000)});
+
+        await expectError(
+            program.methods.queueGovernanceAction(action, new anchor.BN((unixTimestamp + BigInt(governanceDelay.toString()) - BigInt(1)).toString()))
+                .accounts({governanceAuthority: dev.publicKey})
+                .signers([dev])
+                .rpc(),
+            "EtaBeforeGovernanceDelay"
+        )
+        await expectError(
+            queueGovernanceAction({setGovernanceDelay: {governanceDelay: new anchor.BN(0)}}),
+            "InvalidGovernanceDelay"
+        )
+
+        const {id, eta} = await queueGovernanceAction(action);
+        await expectError(
+            program.methods.executeGovernanceAction(id).accounts({proposer: dev.publicKey}).rpc(),
+            "GovernanceActionNotReady"
+        )
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.platformMintFee.toNumber()).equal(platformMintFee.toNumber(), "a queued action should not apply before its eta")
+
+        await expectError(
+            program.methods.cancelGovernanceAction(id)
+                .accounts({governanceAuthority: badActor.publicKey, proposer: dev.publicKey})
+                .signers([badActor])
+                .rpc(),
+            "UnauthorizedGovernanceAuthority"
+        )
+        await program.methods.cancelGovernanceAction(id)
+            .accounts({governanceAuthority: dev.publicKey, proposer: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        expect(await provider.connection.getAccountInfo(governanceActionAddress(id))).equal(null, "a cancelled action should be closed")
+
+        // an action left in the queue past the grace period can only be cancelled
+        const stale = await queueGovernanceAction(action);
+        await setUnixTimestamp(BigInt(stale.eta.toString()) + BigInt(14 * 24 * 3600 + 1));
+        await expectError(
+            program.methods.executeGovernanceAction(stale.id).accounts({proposer: dev.publicKey}).rpc(),
+            "GovernanceActionExpired"
+        )
+        await program.methods.cancelGovernanceAction(stale.id)
+            .accounts({governanceAuthority: dev.publicKey, proposer: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        await setUnixTimestamp(unixTimestamp);
+
+        await executeGovernanceAction({setGovernanceDelay: {governanceDelay: new anchor.BN(7_200)}});
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.governanceDelay.toNumber()).equal(7_200)
+        await executeGovernanceAction({setGovernanceDelay: {governanceDelay: governanceDelay}});
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.governanceDelay.toNumber()).equal(governanceDelay.toNumber())
+        expect(solStrategy.platformMintFee.toNumber()).equal(platformMintFee.toNumber())
+    })
+
+    /// Initial pool investment
+    await it("should invest sol at one solxr per sol and emit the nav snapshot", async () => {
+        const investor = Keypair.generate();
+        await fundAccount(investor, 10)
+        const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+        const events = await rpcWithEvents(
+            program.methods.invest(amount).accounts({investor: investor.publicKey}),
+            [investor]
+        );
+
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solxr = await getMint(provider.connection, tokenPDA)
+        const backing = BigInt(solStrategy.solInTreasury.toString()) + BigInt(solStrategy.stakedLamports.toString()) + BigInt(solStrategy.stakePoolLamports.toString());
+        const invested = findEvent(events, "Invested");
+        expect(invested.investor.toBase58()).equal(investor.publicKey.toBase58())
+        expect(invested.solAmount.toString()).equal(amount.toString())
+        expect(invested.solxrMinted.toString()).equal(amount.toString())
+        expect(invested.solInTreasury.toString()).equal(solStrategy.solInTreasury.toString())
+        expect(invested.nav.toString()).equal((backing * BigInt(LAMPORTS_PER_SOL) / solxr.supply).toString())
+    })
+
+    /// Whitelist Dutch auction
+    await it("should convert an auction whitelist edition at the price paid", async () => {
+        const buyer = Keypair.generate();
+        await fundAccount(buyer, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const setTime = async (timestamp: number) => {
+            const clock = await provider.context.banksClient.getClock();
+            provider.context.setClock(
+                new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(timestamp)),
+            );
+        }
+
+        await program.methods.sellWhitelist(
+            "Auction Whitelist",
+            "AWL",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(2 * LAMPORTS_PER_SOL),
+            new anchor.BN(now + 200),
+            new anchor.BN(now + 300),
+            new anchor.BN(10),
+            new anchor.BN(2),
+            new anchor.BN(now),
+            new anchor.BN(now + 100),
+            false,
+            {startPrice: new anchor.BN(2 * LAMPORTS_PER_SOL), floorPrice: new anchor.BN(LAMPORTS_PER_SOL), decay: {linear: {}}},
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const whitelistId = new anchor.BN(solStrategy.nextWhitelistId.toNumber() - 1);
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(whitelistId.toNumber()));
+        const [whitelistPDA] = PublicKey.findProgramAddressSync([Buffer.from("whitelist"), idBuffer], program.programId);
+
+        // half way through the sale the price is down to 1.5 SOL
+        await setTime(now + 50);
+        const auctionPrice = 1.5 * LAMPORTS_PER_SOL;
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+        tx.add(
+            await program.methods
+                .buyWhitelist(whitelistId, new anchor.BN(1), null)
+                .accounts({buyer: buyer.publicKey})
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [buyer]);
+        const record = await program.account.whitelistNftRecord.fetch(whitelistEditionRecord(whitelistPDA, 1))
+        expect(record.paidPrice.toNumber()).equal(auctionPrice)
+
+        await setTime(now + 200);
+        await expectError(
+            program.methods.convertWhitelist(whitelistId, new anchor.BN(1), new anchor.BN(2 * LAMPORTS_PER_SOL))
+                .accounts({buyer: buyer.publicKey})
+                .signers([buyer])
+                .rpc(),
+            "SlippageExceeded"
+        )
+
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const solxrIssuedBefore = solStrategy.solxrIssued.toNumber();
+        await program.methods.convertWhitelist(whitelistId, new anchor.BN(1), new anchor.BN(auctionPrice))
+            .accounts({buyer: buyer.publicKey})
+            .signers([buyer])
+            .rpc();
+        const account = await getAccount(provider.connection, await getAssociatedTokenAddress(tokenPDA, buyer.publicKey));
+        expect(Number(account.amount)).equal(auctionPrice)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solxrIssued.toNumber()).equal(solxrIssuedBefore + auctionPrice)
+        await setTime(now);
+    })
+
+    /// Pre-funded stake account
+    await it("should create a stake account at an address that already holds lamports", async () => {
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const stakeId = solStrategy.nextStakeId;
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(stakeId.toNumber()));
+        const [stakeAccountPDA] = PublicKey.findProgramAddressSync([Buffer.from("stake_account"), idBuffer], program.programId);
+        const stakedLamports = solStrategy.stakedLamports.toNumber();
+
+        // anyone can send lamports to the next stake account address before it is created
+        const donation = LAMPORTS_PER_SOL / 100;
+        await provider.sendAndConfirm(
+            new Transaction().add(SystemProgram.transfer({fromPubkey: dev.publicKey, toPubkey: stakeAccountPDA, lamports: donation})),
+            [dev]
+        );
+
+        const amount = 2 * LAMPORTS_PER_SOL;
+        await program.methods.createStake(new anchor.BN(amount))
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        const stakeAccount = await provider.connection.getAccountInfo(stakeAccountPDA);
+        expect(stakeAccount.owner.toBase58()).equal(StakeProgram.programId.toBase58())
+        expect(stakeAccount.lamports).equal(amount + donation)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.stakedLamports.toNumber()).equal(stakedLamports + amount)
+    })
+
+    /// Initial pool cap
+    await it("should keep the initial pool closed once filled even after redemptions", async () => {
+        const investor = Keypair.generate();
+        await fundAccount(investor, 10)
+        const amount = new anchor.BN(LAMPORTS_PER_SOL);
+
+        // leave room for a single investment
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const initialPoolInvested = solStrategy.initialPoolInvested;
+        await setInitialPoolInvested(solStrategy.initialPoolCap.sub(amount));
+        await program.methods.invest(amount).accounts({investor: investor.publicKey}).signers([investor]).rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.initialPoolInvested.toString()).equal(solStrategy.initialPoolCap.toString())
+        await expectError(
+            program.methods.invest(amount).accounts({investor: investor.publicKey}).signers([investor]).rpc(),
+            "InitialSolCapError"
+        )
+
+        // redeeming lowers sol in treasury but must not reopen the 1:1 pool
+        const redemptionCap = solStrategy.redemptionCapPerEpoch;
+        await executeGovernanceAction(redemptionParams(true, null, new anchor.BN(1_000 * LAMPORTS_PER_SOL)));
+        await program.methods.redeemSolxr(amount, new anchor.BN(0))
+            .accounts({redeemer: investor.publicKey})
+            .signers([investor])
+            .rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.initialPoolInvested.toString()).equal(solStrategy.initialPoolCap.toString())
+        await expectError(
+            program.methods.invest(amount).accounts({investor: investor.publicKey}).signers([investor]).rpc(),
+            "InitialSolCapError"
+        )
+
+        await executeGovernanceAction(redemptionParams(false, null, redemptionCap));
+        await setInitialPoolInvested(initialPoolInvested.add(amount));
+    })
+
+    /// Initial pool cap and staking
+    await it("should keep the initial pool closed once filled after treasury sol is staked", async () => {
+        const investor = Keypair.generate();
+        await fundAccount(investor, 10)
+        const amount = new anchor.BN(LAMPORTS_PER_SOL);
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const initialPoolInvested = solStrategy.initialPoolInvested;
+        await setInitialPoolInvested(solStrategy.initialPoolCap);
+
+        // moving treasury sol into a stake account lowers sol in treasury, not the invested counter
+        const solInTreasury = solStrategy.solInTreasury;
+        await program.methods.createStake(new anchor.BN(2 * LAMPORTS_PER_SOL))
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solInTreasury.toString()).equal(solInTreasury.sub(new anchor.BN(2 * LAMPORTS_PER_SOL)).toString())
+        expect(solStrategy.initialPoolInvested.toString()).equal(solStrategy.initialPoolCap.toString())
+        await expectError(
+            program.methods.invest(amount).accounts({investor: investor.publicKey}).signers([investor]).rpc(),
+            "InitialSolCapError"
+        )
+
+        await setInitialPoolInvested(initialPoolInvested);
+    })
+
+    /// Overwrites the initial pool counter to fill the pool without investing thousands of Sol
+    async function setInitialPoolInvested(initialPoolInvested: anchor.BN) {
+        const account = await provider.connection.getAccountInfo(solStrategyPDA);
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const data = await program.coder.accounts.encode("SolStrategy", {...solStrategy, initialPoolInvested});
+        provider.context.setAccount(solStrategyPDA, {...account, data: Buffer.concat([data, account.data.subarray(data.length)])});
+    }
+
+    /// Late bond buyer
+    await it("should not pay a late bond buyer the coupons of periods before the purchase", async () => {
+        const holder = Keypair.generate();
+        await fundAccount(holder, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const price = new anchor.BN(LAMPORTS_PER_SOL);
+        const coupon = price.toNumber() / 100;
+        const setTime = async (timestamp: number) => {
+            const clock = await provider.context.banksClient.getClock();
+            provider.context.setClock(
+                new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(timestamp)),
+            );
+        }
+
+        // the sale stays open past the first coupon period
+        await program.methods.sellBond(
+            "Late Coupon Bond",
+            "LCB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 300),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(10),
+            price,
+            new anchor.BN(1),
+            new anchor.BN(now),
+            new anchor.BN(now + 250),
+            new anchor.BN(10_000_000),
+            new anchor.BN(100),
+            new anchor.BN(0),
+            new anchor.BN(now + 1000),
+            null,
+            null,
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondId = new anchor.BN(solStrategy.nextBondId.toNumber() - 1);
+        const solFromBondBefore = solStrategy.solFromBond.toNumber();
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(bondId.toNumber()));
+        const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+        const editionBuffer = Buffer.alloc(8);
+        editionBuffer.writeBigUInt64LE(BigInt(1));
+        const [bondEditionRecordPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("bond_edition_record"), bondPDA.toBuffer(), editionBuffer],
+            program.programId
+        );
+
+        // one of the three periods has already elapsed when the holder buys
+        await setTime(now + 150);
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+        tx.add(
+            await program.methods
+                .buyBond(bondId, new anchor.BN(1), null)
+                .accounts({buyer: holder.publicKey})
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [holder]);
+
+        const bondEditionRecord = await program.account.bondEditionRecord.fetch(bondEditionRecordPDA)
+        expect(bondEditionRecord.claimedCouponPeriods.toNumber()).equal(1, "the elapsed period should count as claimed")
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore + price.toNumber() + 2 * coupon, "only the remaining coupons should be reserved")
+
+        await expectError(
+            program.methods.claimCoupon(bondId, new anchor.BN(1))
+                .accounts({holder: holder.publicKey})
+                .signers([holder])
+                .rpc(),
+            "NoCouponDue"
+        )
+
+        await setTime(now + 201);
+        const holderBefore = await provider.connection.getAccountInfo(holder.publicKey);
+        await program.methods.claimCoupon(bondId, new anchor.BN(1))
+            .accounts({holder: holder.publicKey})
+            .signers([holder])
+            .rpc();
+        const holderAfter = await provider.connection.getAccountInfo(holder.publicKey);
+        expect(holderAfter.lamports - holderBefore.lamports).equal(coupon - 5000)
+        await setTime(now);
+    })
+
+    /// Auction bond coupons
+    await it("should pay the coupons of an auction bond on the price paid", async () => {
+        const holder = Keypair.generate();
+        await fundAccount(holder, 10)
+        const currentClock = await provider.context.banksClient.getClock();
+        const now = Number(currentClock.unixTimestamp);
+        const setTime = async (timestamp: number) => {
+            const clock = await provider.context.banksClient.getClock();
+            provider.context.setClock(
+                new Clock(clock.slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(timestamp)),
+            );
+        }
+
+        // the auction starts at twice the face price and reaches it at the end of the sale
+        await program.methods.sellBond(
+            "Auction Coupon Bond",
+            "ACB",
+            "https://bafybeiauoz3l4ssofopdg36a4teo5at6paavgjzvyhcyr5e4bvk5fwqlpy.ipfs.w3s.link/metadata.json",
+            new anchor.BN(now + 300),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(10),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(1),
+            new anchor.BN(now),
+            new anchor.BN(now + 100),
+            new anchor.BN(10_000_000),
+            new anchor.BN(100),
+            new anchor.BN(0),
+            new anchor.BN(now + 1000),
+            null,
+            {startPrice: new anchor.BN(2 * LAMPORTS_PER_SOL), floorPrice: new anchor.BN(LAMPORTS_PER_SOL), decay: {linear: {}}},
+            null,
+        )
+            .accounts({governanceAuthority: dev.publicKey})
+            .signers([dev])
+            .rpc();
+
+        let solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const bondId = new anchor.BN(solStrategy.nextBondId.toNumber() - 1);
+        const solFromBondBefore = solStrategy.solFromBond.toNumber();
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(bondId.toNumber()));
+        const [bondPDA] = PublicKey.findProgramAddressSync([Buffer.from("bond"), idBuffer], program.programId);
+        const editionBuffer = Buffer.alloc(8);
+        editionBuffer.writeBigUInt64LE(BigInt(1));
+        const [bondEditionRecordPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("bond_edition_record"), bondPDA.toBuffer(), editionBuffer],
+            program.programId
+        );
+
+        await setTime(now + 50);
+        const auctionPrice = 1.5 * LAMPORTS_PER_SOL;
+        const coupon = auctionPrice / 100;
+        const tx = new anchor.web3.Transaction();
+        tx.add(ComputeBudgetProgram.setComputeUnitLimit({units: 300_000}));
+        tx.add(
+            await program.methods
+                .buyBond(bondId, new anchor.BN(1), null)
+                .accounts({buyer: holder.publicKey})
+                .instruction()
+        );
+        await provider.sendAndConfirm(tx, [holder]);
+
+        const bondEditionRecord = await program.account.bondEditionRecord.fetch(bondEditionRecordPDA)
+        expect(bondEditionRecord.paidPrice.toNumber()).equal(auctionPrice)
+        solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        expect(solStrategy.solFromBond.toNumber()).equal(solFromBondBefore + auctionPrice + 3 * coupon, "coupons should be reserved on the price paid")
+        const bond = await program.account.bond.fetch(bondPDA)
+        expect(bond.solReserved.toNumber()).equal(auctionPrice + 3 * coupon)
+
+        await setTime(now + 101);
+        const holderBefore = await provider.connection.getAccountInfo(holder.publicKey);
+        await program.methods.claimCoupon(bondId, new anchor.BN(1))
+            .accounts({holder: holder.publicKey})
+            .signers([holder])
+            .rpc();
+        const holderAfter = await provider.connection.getAccountInfo(holder.publicKey);
+        expect(holderAfter.lamports - holderBefore.lamports).equal(coupon - 5000, "the coupon should be a share of the price paid")
+        await setTime(now);
+    })
+
+    async function getMasterEdition(mintAddress: PublicKey): Promise<MasterEdition> {
+        // Get master edition PDA
+        const [editionPDA] = PublicKey.findProgramAddressSync(
+            [
+                Buffer.from('metadata'),
+                METADATA_PROGRAM_ID.toBuffer(),
+                mintAddress.toBuffer(),
+                Buffer.from('edition')
+            ],
+            METADATA_PROGRAM_ID
+        );
+        // Fetch the accounts
+        const editionAccountInfo = await provider.connection.getAccountInfo(editionPDA);
+
+
+        return deserializeMasterEdition({
+            executable: editionAccountInfo.executable,
+            rentEpoch: BigInt(editionAccountInfo.rentEpoch),
+            lamports: lamports(editionAccountInfo.lamports),
+            owner: editionAccountInfo.owner.toBase58() as MTPublicKey,
+            data: editionAccountInfo.data,
+            publicKey: editionPDA.toBase58() as MTPublicKey
+        })
+    }
+
+    async function getEdition(mintAddress: PublicKey): Promise<Edition> {
+        // Get master edition PDA
+        const [editionPDA] = PublicKey.findProgramAddressSync(
+            [
+                Buffer.from('metadata'),
+                METADATA_PROGRAM_ID.toBuffer(),
+                mintAddress.toBuffer(),
+                Buffer.from('edition')
+            ],
+            METADATA_PROGRAM_ID
+        );
+        // Fetch the accounts
+        const editionAccountInfo = await provider.connection.getAccountInfo(editionPDA);
+
+        return deserializeEdition({
+            executable: editionAccountInfo.executable,
+            rentEpoch: BigInt(editionAccountInfo.rentEpoch),
+            lamports: lamports(editionAccountInfo.lamports),
+            owner: editionAccountInfo.owner.toBase58() as MTPublicKey,
+            data: editionAccountInfo.data,
+            publicKey: editionPDA.toBase58() as MTPublicKey
+        })
+    }
+
+    async function transferTokens(
+        sender: Keypair,
+        receiver: PublicKey,
+        mint: PublicKey,
+        amount: number
+    ): Promise<void> {
+        const sourceATA = await getAssociatedTokenAddress(mint, sender.publicKey);
+
+        const destinationATA = await getAssociatedTokenAddress(mint, receiver);
+
+        const createATAIx = createAssociatedTokenAccountInstruction(
+            sender.publicKey,
+            destinationATA,
+            receiver,
+            mint
+        );
+
+        const transferIx = createTransferInstruction(
+            sourceATA,
+            destinationATA,
+            sender.publicKey,
+            amount
+        );
+
+        const tx = new Transaction().add(createATAIx).add(transferIx);
+        await provider.sendAndConfirm(tx, [sender]);
+    }
+
+    function strategyParams(params: {[param: string]: anchor.BN | null}) {
+        return {
+            updateStrategyParams: {
+                platformMintFee: null,
+                maxPlatformMintFee: null,
+                maxMintPerWallet: null,
+                minPremiumNavRatio: null,
+                navGrowthRate: null,
+                mintingRounds: null,
+                mintDuration: null,
+                ...params,
+            }
+        }
+    }
+
+    function redemptionParams(allowRedemption: boolean | null, redemptionFee: anchor.BN | null, redemptionCapPerEpoch: anchor.BN | null) {
+        return {updateRedemptionParams: {allowRedemption, redemptionFee, redemptionCapPerEpoch}}
+    }
+
+    function governanceActionAddress(id: anchor.BN): PublicKey {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("governance_action"), id.toArrayLike(Buffer, "le", 8)],
+            program.programId
+        )[0];
+    }
+
+    /// Queues a timelocked action with the earliest eta allowed and returns its id and eta
+    async function queueGovernanceAction(action: any, governance: Keypair = dev) {
+        const solStrategy = await program.account.solStrategy.fetch(solStrategyPDA)
+        const clock = await provider.context.banksClient.getClock();
+        const eta = new anchor.BN((clock.unixTimestamp + BigInt(solStrategy.governanceDelay.toString())).toString());
+        await program.methods.queueGovernanceAction(action, eta)
+            .accounts({governanceAuthority: governance.publicKey})
+            .signers([governance])
+            .rpc();
+        return {id: solStrategy.nextGovernanceActionId, eta};
+    }
+
+    async function setUnixTimestamp(unixTimestamp: bigint) {
+        const currentClock = await provider.context.banksClient.getClock();
+        provider.context.setClock(
+            new Clock(
+                currentClock.slot,
+                currentClock.epochStartTimestamp,
+                currentClock.epoch,
+                currentClock.leaderScheduleEpoch,
+                unixTimestamp,
+            ),
+        );
+    }
+
+    /// Queues a timelocked action and executes it at its eta, the clock is moved back afterwards so
+    /// the timing of the other tests is left untouched
+    async function executeGovernanceAction(action: any, governance: Keypair = dev) {
+        const {unixTimestamp} = await provider.context.banksClient.getClock();
+        const {id, eta} = await queueGovernanceAction(action, governance);
+        await setUnixTimestamp(BigInt(eta.toString()));
+        await program.methods.executeGovernanceAction(id)
+            .accounts({proposer: governance.publicKey, priceObservation: priceObservationPDA})
+            .rpc();
+        await setUnixTimestamp(unixTimestamp);
+    }
+
+    /// Sends the transaction and decodes the events emitted in its program logs
+    async function rpcWithEvents(builder: any, signers: Keypair[]) {
+        const tx: Transaction = await builder.transaction();
+        [tx.recentBlockhash] = await provider.context.banksClient.getLatestBlockhash();
+        tx.feePayer = provider.wallet.publicKey;
+        tx.partialSign(...signers);
+        await provider.wallet.signTransaction(tx);
+        const meta = await provider.context.banksClient.processTransaction(tx);
+        return [...eventParser.parseLogs(meta.logMessages)];
+    }
+
+    function findEvent(events: anchor.Event[], name: string) {
+        const event = events.find((event) => event.name.toLowerCase() === name.toLowerCase());
+        expect(event, `${name} should be emitted`).not.equal(undefined);
+        return event.data as any;
+    }
+
+    async function fundAccount(keyPair: Keypair, amount: number) {
+        const instruction = SystemProgram.transfer({
+            fromPubkey: providerKeypair.publicKey,
+            toPubkey: keyPair.publicKey,
+            lamports: amount * LAMPORTS_PER_SOL,
+        });
+        const transaction = new Transaction().add(instruction);
+        await provider.sendAndConfirm(transaction, [providerKeypair.payer]);
+    }
+
+    /// Writes a Pyth-style price account with the given aggregate price and a -9 exponent
+    function setPriceAccount(address: PublicKey, price: bigint, conf: bigint, publishTime: bigint) {
+        const data = Buffer.alloc(240);
+        data.writeUInt32LE(0xa1b2c3d4, 0); // magic
+        data.writeUInt32LE(2, 4); // version
+        data.writeUInt32LE(3, 8); // price account
+        data.writeInt32LE(-9, 20); // exponent
+        data.writeBigInt64LE(publishTime, 96);
+        data.writeBigInt64LE(price, 208);
+        data.writeBigUInt64LE(conf, 216);
+        data.writeUInt32LE(1, 224); // trading
+        provider.context.setAccount(address, {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: SystemProgram.programId,
+            executable: false,
+        });
+    }
+
+    async function expectError(promise: Promise<string>, errorCode: string) {
+        try {
+            await promise;
+            expect.fail("Expected an error but the instruction succeeded");
+        } catch (error: any) {
+            let msg = error.message as string
+            console.log(msg, "->", errorCode)
+            expect(msg.includes('AnchorError')).true
+            expect(msg.includes(`Error Code: ${errorCode}`)).true
+        }
     }
 });
\ No newline at end of file